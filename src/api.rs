@@ -1,6 +1,12 @@
 use serde_json::Value;
 
-pub const NHL_API_URL: &'static str = "https://api-web.nhle.com/v1";
+pub const NHL_API_URL: &str = "https://api-web.nhle.com/v1";
+pub const NHL_STATS_API_URL: &str = "https://api.nhle.com/stats/rest/en";
+pub const NHL_SEARCH_API_URL: &str = "https://search.d3.nhle.com/api/v1/search";
+
+/// Game types the stats and leaders endpoints take
+pub const REGULAR_SEASON: i64 = 2;
+pub const PLAYOFFS: i64 = 3;
 
 pub async fn nhl_api_request(
     client: &reqwest::Client,
@@ -9,4 +15,70 @@ pub async fn nhl_api_request(
     let response = client.get(url).send().await?;
     let json: Value = response.json().await?;
     Ok(json)
-} 
\ No newline at end of file
+}
+
+/// Convert a human season like "2022-23" (or "2022-2023" / "20222023") into the api's season id "20222023"
+pub fn parse_season(season: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let digits: String = season.chars().filter(|c| c.is_ascii_digit()).collect();
+    let start: i32 = digits
+        .get(0..4)
+        .and_then(|y| y.parse().ok())
+        .ok_or_else(|| format!("Invalid season '{}'. Use a season like 2022-23", season))?;
+    let end = &digits[4..];
+
+    let valid = match end.len() {
+        2 => end == format!("{:02}", (start + 1) % 100),
+        4 => end == (start + 1).to_string(),
+        _ => false,
+    };
+    if !valid {
+        return Err(format!("Invalid season '{}'. Use a season like 2022-23", season).into());
+    }
+
+    Ok(format!("{}{}", start, start + 1))
+}
+
+/// Season id (e.g. 20232024) of a season like 2023-24, or of the current season
+pub async fn season_id(
+    client: &reqwest::Client,
+    season: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match season {
+        Some(season) => parse_season(season),
+        None => {
+            let url = format!("{}/standings/now", NHL_API_URL);
+            let standings = nhl_api_request(client, &url).await?;
+            let season_id = standings["standings"][0]["seasonId"]
+                .as_i64()
+                .ok_or("Couldn't find the current season")?;
+            Ok(season_id.to_string())
+        }
+    }
+}
+
+/// Url of a stats REST API report (e.g. "skater/summary") filtered by a cayenne expression,
+/// a limit of -1 returns every row
+pub fn stats_report_url(
+    report: &str,
+    cayenne: &str,
+    sort: Option<&str>,
+    start: usize,
+    limit: i64,
+) -> Result<reqwest::Url, Box<dyn std::error::Error>> {
+    let start = start.to_string();
+    let limit = limit.to_string();
+    let mut params = vec![
+        ("isAggregate", "false"),
+        ("isGame", "false"),
+        ("start", start.as_str()),
+        ("limit", limit.as_str()),
+        ("cayenneExp", cayenne),
+    ];
+    if let Some(sort) = sort {
+        params.push(("sort", sort));
+    }
+    Ok(reqwest::Url::parse_with_params(
+        &format!("{}/{}", NHL_STATS_API_URL, report),
+        &params,
+    )?)
+}
@@ -1,6 +1,7 @@
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{DateTime, Duration, Local, NaiveDate};
 use colored::Colorize;
 use dialoguer::{Select, theme::ColorfulTheme};
+use serde_json::Value;
 use crate::api::{NHL_API_URL, nhl_api_request};
 
 pub async fn display_boxscore(
@@ -71,6 +72,12 @@ pub async fn display_boxscore(
     }
     println!("{}", separator);
 
+    // Games that haven't started have no scoring yet, show a preview instead
+    if game_state == "FUT" || game_state == "PRE" {
+        display_preview(client, game_id, &game).await?;
+        return Ok(());
+    }
+
     // Score by Period
     println!("\n{:^70}", "SCORING SUMMARY".bold());
     println!("{}", "-".repeat(70));
@@ -80,33 +87,10 @@ pub async fn display_boxscore(
     );
 
     // Team scoring
-    if let Some(scoring) = game["summary"]["scoring"].as_array() {
-        let mut away_scores = vec![0; 5];
-        let mut home_scores = vec![0; 5];
-
-        for (i, period) in scoring.iter().enumerate() {
-            if let Some(goals) = period["goals"].as_array() {
-                for goal in goals {
-                    if goal["teamAbbrev"]["default"].as_str() == game["awayTeam"]["abbrev"].as_str() {
-                        away_scores[i] += 1;
-                    } else {
-                        home_scores[i] += 1;
-                    }
-                }
-            }
-        }
-
+    if let Some((away_scores, home_scores)) = period_scores(&game) {
         // Print away team scoring
         print!("{:>20}", away_team);
         let away_total: i32 = away_scores.iter().sum();
-        if away_scores.len() == 5 {
-            away_scores[3] = away_scores[3] + away_scores[4];
-            away_scores.pop();
-        }
-        if home_scores.len() == 5 {
-            home_scores[3] = home_scores[3] + home_scores[4];
-            home_scores.pop();
-        }
         for score in away_scores {
             print!(" {:>8}", score);
         }
@@ -130,13 +114,13 @@ pub async fn display_boxscore(
             if period_idx < 3 {
                 println!("\n{}", format!("Period {}", period_idx + 1).bold());
             } else if period_idx == 3 {
-                println!("\n{}", format!("Overtime").bold());
+                println!("\n{}", "Overtime".bold());
             } else if period_idx == 4 {
-                println!("\n{}", format!("Shootout").bold());
+                println!("\n{}", "Shootout".bold());
             }
 
             if let Some(goals) = period["goals"].as_array() {
-                if goals.len() == 0 {
+                if goals.is_empty() {
                     println!("No goals scored in this period");
                     continue;
                 }
@@ -177,6 +161,275 @@ pub async fn display_boxscore(
     Ok(())
 }
 
+/// Goals by period for the away and home teams from a game's landing page. Every overtime
+/// period and the shootout count towards the OT column, the fourth one.
+pub fn period_scores(game: &Value) -> Option<(Vec<i32>, Vec<i32>)> {
+    let scoring = game["summary"]["scoring"].as_array()?;
+    let mut away_scores = vec![0; 4];
+    let mut home_scores = vec![0; 4];
+
+    for (i, period) in scoring.iter().enumerate() {
+        let column = i.min(3);
+        if let Some(goals) = period["goals"].as_array() {
+            for goal in goals {
+                if goal["teamAbbrev"]["default"].as_str() == game["awayTeam"]["abbrev"].as_str() {
+                    away_scores[column] += 1;
+                } else {
+                    home_scores[column] += 1;
+                }
+            }
+        }
+    }
+
+    Some((away_scores, home_scores))
+}
+
+async fn display_preview(
+    client: &reqwest::Client,
+    game_id: &str,
+    game: &Value,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let away_abbrev = game["awayTeam"]["abbrev"].as_str().unwrap_or("");
+    let home_abbrev = game["homeTeam"]["abbrev"].as_str().unwrap_or("");
+
+    // Venue and broadcast info
+    let venue = game["venue"]["default"].as_str().unwrap_or("Unknown Venue");
+    let location = game["venueLocation"]["default"].as_str().unwrap_or("");
+    let start_time = game["startTimeUTC"]
+        .as_str()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Local).format("%-I:%M %p").to_string())
+        .unwrap_or_else(|| "TBD".to_string());
+
+    println!("\n{:^70}", "GAME INFO".bold());
+    println!("{}", "-".repeat(70));
+    if location.is_empty() {
+        println!("{:<12} {}", "Venue", venue);
+    } else {
+        println!("{:<12} {}, {}", "Venue", venue, location);
+    }
+    println!("{:<12} {}", "Start", start_time);
+    if let Some(broadcasts) = game["tvBroadcasts"].as_array() {
+        let networks: Vec<_> = broadcasts
+            .iter()
+            .map(|b| {
+                let market = match b["market"].as_str().unwrap_or("") {
+                    "H" => home_abbrev,
+                    "A" => away_abbrev,
+                    _ => "National",
+                };
+                format!(
+                    "{} ({} {})",
+                    b["network"].as_str().unwrap_or(""),
+                    market,
+                    b["countryCode"].as_str().unwrap_or("")
+                )
+            })
+            .collect();
+        if !networks.is_empty() {
+            println!("{:<12} {}", "Broadcast", networks.join(", "));
+        }
+    }
+
+    // Team records from the current standings
+    let url = format!("{}/standings/now", NHL_API_URL);
+    let standings = nhl_api_request(client, &url).await?;
+    let find_team = |abbrev: &str| {
+        standings["standings"].as_array().and_then(|teams| {
+            teams
+                .iter()
+                .find(|t| t["teamAbbrev"]["default"].as_str() == Some(abbrev))
+                .cloned()
+        })
+    };
+    let away_standing = find_team(away_abbrev).unwrap_or(Value::Null);
+    let home_standing = find_team(home_abbrev).unwrap_or(Value::Null);
+
+    let record = |team: &Value| {
+        format!(
+            "{}-{}-{}",
+            team["wins"].as_i64().unwrap_or(0),
+            team["losses"].as_i64().unwrap_or(0),
+            team["otLosses"].as_i64().unwrap_or(0)
+        )
+    };
+    let last_10 = |team: &Value| {
+        format!(
+            "{}-{}-{}",
+            team["l10Wins"].as_i64().unwrap_or(0),
+            team["l10Losses"].as_i64().unwrap_or(0),
+            team["l10OtLosses"].as_i64().unwrap_or(0)
+        )
+    };
+    let streak = |team: &Value| {
+        format!(
+            "{}{}",
+            team["streakCode"].as_str().unwrap_or("-"),
+            team["streakCount"].as_i64().unwrap_or(0)
+        )
+    };
+
+    println!("\n{:^70}", "TEAM COMPARISON".bold());
+    println!("{}", "-".repeat(70));
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "",
+        away_abbrev.bold(),
+        home_abbrev.bold()
+    );
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "Record",
+        record(&away_standing),
+        record(&home_standing)
+    );
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "Points",
+        away_standing["points"].as_i64().unwrap_or(0),
+        home_standing["points"].as_i64().unwrap_or(0)
+    );
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "Last 10",
+        last_10(&away_standing),
+        last_10(&home_standing)
+    );
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "Streak",
+        streak(&away_standing),
+        streak(&home_standing)
+    );
+
+    // Season series so far
+    let url = format!("{}/gamecenter/{}/right-rail", NHL_API_URL, game_id);
+    let right_rail = nhl_api_request(client, &url).await?;
+    println!("\n{:^70}", "SEASON SERIES".bold());
+    println!("{}", "-".repeat(70));
+    let series_games: Vec<&Value> = right_rail["seasonSeries"]
+        .as_array()
+        .map(|games| {
+            games
+                .iter()
+                .filter(|g| matches!(g["gameState"].as_str(), Some("FINAL") | Some("OFF")))
+                .collect()
+        })
+        .unwrap_or_default();
+    if series_games.is_empty() {
+        println!("First meeting of the season");
+    } else {
+        for series_game in series_games {
+            let date = series_game["gameDate"].as_str().unwrap_or("");
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|d| d.format("%b %d").to_string())
+                .unwrap_or_else(|_| date.to_string());
+            let last_period = series_game["gameOutcome"]["lastPeriodType"]
+                .as_str()
+                .unwrap_or("REG");
+            println!(
+                "{:<8} {:>4} {:>2} @ {:<2} {:<4} {}",
+                date,
+                series_game["awayTeam"]["abbrev"].as_str().unwrap_or(""),
+                series_game["awayTeam"]["score"].as_i64().unwrap_or(0),
+                series_game["homeTeam"]["score"].as_i64().unwrap_or(0),
+                series_game["homeTeam"]["abbrev"].as_str().unwrap_or(""),
+                if last_period == "REG" { "" } else { last_period }
+            );
+        }
+        let wins = &right_rail["seasonSeriesWins"];
+        println!(
+            "Series: {} {} - {} {}",
+            away_abbrev,
+            wins["awayTeamWins"].as_i64().unwrap_or(0),
+            home_abbrev,
+            wins["homeTeamWins"].as_i64().unwrap_or(0)
+        );
+    }
+
+    // Goalies and top scorers from each team's season stats
+    let mut club_stats = Vec::new();
+    for abbrev in [away_abbrev, home_abbrev] {
+        let url = format!("{}/club-stats/{}/now", NHL_API_URL, abbrev);
+        club_stats.push((abbrev, nhl_api_request(client, &url).await?));
+    }
+
+    println!("\n{:^70}", "LIKELY GOALIES".bold());
+    println!("{}", "-".repeat(70));
+    println!(
+        "{}",
+        format!(
+            "{:<4} {:<24} {:>3} {:>9} {:>5} {:>6} {:>3}",
+            "Team", "Goalie", "GP", "Record", "GAA", "SV%", "SO"
+        )
+        .bold()
+        .underline()
+    );
+    for (abbrev, stats) in &club_stats {
+        if let Some(goalies) = stats["goalies"].as_array() {
+            let mut goalies = goalies.clone();
+            goalies.sort_by_key(|g| -(g["gamesPlayed"].as_i64().unwrap_or(0)));
+            for goalie in goalies.iter().take(2) {
+                println!(
+                    "{:<4} {:<24} {:>3} {:>9} {:>5.2} {:>6.3} {:>3}",
+                    abbrev,
+                    format!(
+                        "{} {}",
+                        goalie["firstName"]["default"].as_str().unwrap_or(""),
+                        goalie["lastName"]["default"].as_str().unwrap_or("")
+                    ),
+                    goalie["gamesPlayed"].as_i64().unwrap_or(0),
+                    format!(
+                        "{}-{}-{}",
+                        goalie["wins"].as_i64().unwrap_or(0),
+                        goalie["losses"].as_i64().unwrap_or(0),
+                        goalie["overtimeLosses"].as_i64().unwrap_or(0)
+                    ),
+                    goalie["goalsAgainstAverage"].as_f64().unwrap_or(0.0),
+                    goalie["savePercentage"].as_f64().unwrap_or(0.0),
+                    goalie["shutouts"].as_i64().unwrap_or(0)
+                );
+            }
+        }
+    }
+
+    println!("\n{:^70}", "TOP SCORERS".bold());
+    println!("{}", "-".repeat(70));
+    println!(
+        "{}",
+        format!(
+            "{:<4} {:<24} {:>3} {:>3} {:>3} {:>3} {:>4}",
+            "Team", "Player", "Pos", "GP", "G", "A", "PTS"
+        )
+        .bold()
+        .underline()
+    );
+    for (abbrev, stats) in &club_stats {
+        if let Some(skaters) = stats["skaters"].as_array() {
+            let mut skaters = skaters.clone();
+            skaters.sort_by_key(|s| -(s["points"].as_i64().unwrap_or(0)));
+            for skater in skaters.iter().take(3) {
+                println!(
+                    "{:<4} {:<24} {:>3} {:>3} {:>3} {:>3} {:>4}",
+                    abbrev,
+                    format!(
+                        "{} {}",
+                        skater["firstName"]["default"].as_str().unwrap_or(""),
+                        skater["lastName"]["default"].as_str().unwrap_or("")
+                    ),
+                    skater["positionCode"].as_str().unwrap_or(""),
+                    skater["gamesPlayed"].as_i64().unwrap_or(0),
+                    skater["goals"].as_i64().unwrap_or(0),
+                    skater["assists"].as_i64().unwrap_or(0),
+                    skater["points"].as_i64().unwrap_or(0).to_string().bold()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn get_list_of_games_for_boxscores(
     client: &reqwest::Client,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -243,6 +496,6 @@ pub async fn get_list_of_games_for_boxscores(
         .default(0)
         .interact()?;
 
-    display_boxscore(&client, &all_games[selection].to_string()).await?;
+    display_boxscore(client, &all_games[selection].to_string()).await?;
     Ok(())
 } 
\ No newline at end of file
@@ -0,0 +1,439 @@
+use colored::Colorize;
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::api::{NHL_API_URL, nhl_api_request};
+use crate::standings::{
+    DIVISION_PLAYOFF_SPOTS, WILD_CARD_SPOTS, games_remaining, team_abbrev, wildcard_standings,
+};
+use crate::tiebreakers::Tiebreaks;
+
+// Clinch markers from weakest to strongest
+const MARKER_ORDER: [char; 4] = ['x', 'y', 'z', 'p'];
+
+pub const CLINCH_LEGEND: &str = "x - Clinched playoff spot   y - Clinched division   \
+                                 z - Clinched conference   p - Presidents' Trophy   e - Eliminated";
+
+fn points(team: &Value) -> i64 {
+    team["points"].as_i64().unwrap_or(0)
+}
+
+/// Most points a team can finish with if it wins every remaining game
+pub fn max_points(team: &Value) -> i64 {
+    points(team) + 2 * games_remaining(team)
+}
+
+/// Count the regular season games every team has left after the date of the standings from
+/// its schedule, stored as `gamesRemaining`. This follows shortened seasons like 2020-21. A
+/// team whose schedule can't be fetched is left without it, and counted as playing 82 games.
+pub async fn load_games_remaining(client: &reqwest::Client, teams: &mut [Value]) {
+    let mut requests = tokio::task::JoinSet::new();
+    for (i, team) in teams.iter().enumerate() {
+        let url = format!(
+            "{}/club-schedule-season/{}/{}",
+            NHL_API_URL,
+            team_abbrev(team),
+            team["seasonId"].as_i64().unwrap_or(0)
+        );
+        let client = client.clone();
+        requests.spawn(async move {
+            let schedule = nhl_api_request(&client, &url)
+                .await
+                .map_err(|e| e.to_string());
+            (i, schedule)
+        });
+    }
+
+    while let Some(response) = requests.join_next().await {
+        let Ok((i, Ok(schedule))) = response else {
+            continue;
+        };
+        let cutoff = teams[i]["date"].as_str().unwrap_or("").to_string();
+        let remaining = schedule["games"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|game| {
+                let date = game["gameDate"].as_str().unwrap_or("");
+                let finished = matches!(game["gameState"].as_str(), Some("FINAL") | Some("OFF"));
+                game["gameType"].as_i64() == Some(2) && (date > cutoff.as_str() || !finished)
+            })
+            .count();
+        teams[i]["gamesRemaining"] = (remaining as i64).into();
+    }
+}
+
+fn same_group<'a>(teams: &'a [Value], team: &Value, key: &str) -> Vec<&'a Value> {
+    teams
+        .iter()
+        .filter(|t| t[key] == team[key] && team_abbrev(t) != team_abbrev(team))
+        .collect()
+}
+
+// Points a team needs to gain, or its rival to drop, to clinch a top `places` finish. The
+// rival is the team with the `places`-th most points still reachable. Zero means clinched.
+pub fn magic_number(team: &Value, others: &[&Value], places: usize) -> i64 {
+    let mut reachable: Vec<i64> = others.iter().map(|t| max_points(t)).collect();
+    reachable.sort_by_key(|p| -p);
+    match reachable.get(places - 1) {
+        Some(rival_max) => (rival_max - points(team) + 1).max(0),
+        None => 0,
+    }
+}
+
+// A team has clinched a top `places` finish when fewer than `places` other teams can still
+// reach its current points. Ties count against the team since tiebreakers can still change.
+pub fn clinched_top(team: &Value, others: &[&Value], places: usize) -> bool {
+    others
+        .iter()
+        .filter(|t| max_points(t) >= points(team))
+        .count()
+        < places
+}
+
+// A team is eliminated from a top `places` finish when at least `places` other teams already
+// have more points than it can reach
+pub fn eliminated_top(team: &Value, others: &[&Value], places: usize) -> bool {
+    others
+        .iter()
+        .filter(|t| points(t) > max_points(team))
+        .count()
+        >= places
+}
+
+// Whether the teams finishing above a team keep it out of the playoffs: at least 3 of its
+// division rivals are above it, and the ones left over after each division's top 3 fill both
+// wild cards. `division_ahead` counts rivals in its own division, `other_ahead` the rest of
+// its conference.
+fn misses_playoffs(division_ahead: usize, other_ahead: usize) -> bool {
+    division_ahead >= DIVISION_PLAYOFF_SPOTS
+        && (division_ahead - DIVISION_PLAYOFF_SPOTS)
+            + other_ahead.saturating_sub(DIVISION_PLAYOFF_SPOTS)
+            >= WILD_CARD_SPOTS
+}
+
+// A team with `points` has clinched a playoff spot when it still gets in even if every
+// conference rival that can reach those points does. Ties count against the team.
+pub fn clinched_playoffs(points: i64, division: &[&Value], rest: &[&Value]) -> bool {
+    let ahead = |teams: &[&Value]| teams.iter().filter(|t| max_points(t) >= points).count();
+    !misses_playoffs(ahead(division), ahead(rest))
+}
+
+// A team that can reach at most `max_points` is eliminated when the rivals already above
+// that keep it out of the playoffs
+pub fn eliminated_playoffs(max_points: i64, division: &[&Value], rest: &[&Value]) -> bool {
+    let ahead = |teams: &[&Value]| teams.iter().filter(|t| points(t) > max_points).count();
+    misses_playoffs(ahead(division), ahead(rest))
+}
+
+// Points a team needs to gain, or its rivals to drop, to clinch a playoff spot. Zero means
+// clinched.
+fn playoff_magic_number(team: &Value, division: &[&Value], rest: &[&Value]) -> i64 {
+    (0..)
+        .find(|gain| clinched_playoffs(points(team) + gain, division, rest))
+        .unwrap_or(0)
+}
+
+// Points a team can drop, or its rivals gain, before it is eliminated. Zero means eliminated,
+// None that it can't be with this few teams.
+fn playoff_tragic_number(team: &Value, division: &[&Value], rest: &[&Value]) -> Option<i64> {
+    let max = max_points(team);
+    (0..=max + 1).find(|drop| eliminated_playoffs(max - drop, division, rest))
+}
+
+// Conference rivals outside a team's division, the teams it competes with for a wild card
+// along with its own division's teams
+fn rest_of_conference<'a>(teams: &'a [Value], team: &Value) -> Vec<&'a Value> {
+    same_group(teams, team, "conferenceName")
+        .into_iter()
+        .filter(|t| t["divisionName"] != team["divisionName"])
+        .collect()
+}
+
+/// Work out clinch and elimination markers from points and games remaining. Making the
+/// playoffs means a top 3 finish in the division or one of the conference's two wild cards.
+pub fn calculate_markers(teams: &[Value]) -> HashMap<String, char> {
+    let mut markers = HashMap::new();
+    let league: Vec<&Value> = teams.iter().collect();
+
+    for team in teams {
+        let division = same_group(teams, team, "divisionName");
+        let conference = same_group(teams, team, "conferenceName");
+        let rest = rest_of_conference(teams, team);
+        let others: Vec<&Value> = league
+            .iter()
+            .copied()
+            .filter(|t| team_abbrev(t) != team_abbrev(team))
+            .collect();
+
+        let marker = if clinched_top(team, &others, 1) {
+            Some('p')
+        } else if clinched_top(team, &conference, 1) {
+            Some('z')
+        } else if clinched_top(team, &division, 1) {
+            Some('y')
+        } else if clinched_playoffs(points(team), &division, &rest) {
+            Some('x')
+        } else if eliminated_playoffs(max_points(team), &division, &rest) {
+            Some('e')
+        } else {
+            None
+        };
+
+        if let Some(marker) = marker {
+            markers.insert(team_abbrev(team).to_string(), marker);
+        }
+    }
+
+    markers
+}
+
+/// Clinch markers for every team, using the league's indicator and falling back to the
+/// calculated one when the league's hasn't caught up yet
+pub fn clinch_markers(teams: &[Value]) -> HashMap<String, char> {
+    let mut markers = calculate_markers(teams);
+
+    for team in teams {
+        let upstream = team["clinchIndicator"]
+            .as_str()
+            .and_then(|indicator| indicator.chars().next());
+        let Some(upstream) = upstream else {
+            continue;
+        };
+
+        let strength = |marker: char| MARKER_ORDER.iter().position(|m| *m == marker);
+        let calculated = markers.get(team_abbrev(team)).copied();
+        let stronger = match calculated {
+            Some(calculated) => strength(calculated) > strength(upstream),
+            None => false,
+        };
+        if !stronger {
+            markers.insert(team_abbrev(team).to_string(), upstream);
+        }
+    }
+
+    markers
+}
+
+fn format_number(number: i64, done: &str) -> String {
+    if number == 0 {
+        done.to_string()
+    } else {
+        number.to_string()
+    }
+}
+
+/// Magic numbers to clinch a playoff spot, the division and the Presidents' Trophy, and the
+/// tragic number for elimination, laid out like the wildcard standings
+pub fn print_magic_numbers(teams: &[Value], tiebreaks: &Tiebreaks) {
+    let header = format!(
+        "{:<24} {:>3} {:>3} {:>3} {:>8} {:>6} {:>6} {:>6}",
+        "Team", "GP", "PTS", "MAX", "PLAYOFF", "TRAGIC", "DIV", "PRES"
+    );
+    let width = header.len();
+    let separator = "-".repeat(width);
+    let league: Vec<&Value> = teams.iter().collect();
+
+    let print_team = |team: &Value| {
+        let division = same_group(teams, team, "divisionName");
+        let rest = rest_of_conference(teams, team);
+        let others: Vec<&Value> = league
+            .iter()
+            .copied()
+            .filter(|t| team_abbrev(t) != team_abbrev(team))
+            .collect();
+
+        let playoff = playoff_magic_number(team, &division, &rest);
+        let tragic = playoff_tragic_number(team, &division, &rest);
+
+        let (playoff, tragic) = match tragic {
+            Some(0) => ("-".normal(), "E".red()),
+            _ if playoff == 0 => ("✓".green(), "-".normal()),
+            Some(tragic) => (playoff.to_string().normal(), tragic.to_string().normal()),
+            None => (playoff.to_string().normal(), "-".normal()),
+        };
+        let division_title = if eliminated_top(team, &division, 1) {
+            "-".to_string()
+        } else {
+            format_number(magic_number(team, &division, 1), "✓")
+        };
+        let presidents = if eliminated_top(team, &others, 1) {
+            "-".to_string()
+        } else {
+            format_number(magic_number(team, &others, 1), "✓")
+        };
+
+        println!(
+            "{:<24} {:>3} {:>3} {:>3} {:>8} {:>6} {:>6} {:>6}",
+            team["teamName"]["default"].as_str().unwrap_or("Unknown"),
+            team["gamesPlayed"].as_i64().unwrap_or(0),
+            points(team).to_string().bold(),
+            max_points(team),
+            playoff,
+            tragic,
+            division_title,
+            presidents
+        );
+    };
+
+    for conference in wildcard_standings(teams, tiebreaks) {
+        println!("\n{}", separator);
+        println!(
+            "{:^width$}",
+            format!("{} CONFERENCE", conference.name.to_uppercase())
+        );
+        println!("{}", separator);
+
+        for (division_name, division_teams) in &conference.divisions {
+            println!("\n{} {}", division_name.bold(), "Division".bold());
+            println!("{}", header.bold().underline());
+            for team in division_teams.iter().take(DIVISION_PLAYOFF_SPOTS) {
+                print_team(team);
+            }
+        }
+
+        println!("\n{}", "Wild Card".bold());
+        println!("{}", header.bold().underline());
+        for (i, team) in conference.wild_card.iter().enumerate() {
+            if i == WILD_CARD_SPOTS {
+                println!("{}", separator);
+            }
+            print_team(team);
+        }
+    }
+
+    println!(
+        "\n{}",
+        "Magic number: points the team needs to gain, or its rivals to drop, to clinch. \
+         Tragic number: points the team can drop, or its rivals gain, before elimination. \
+         Both assume every rival can win all its remaining games, ignoring games rivals \
+         still play against each other, so they can be higher than strictly needed."
+            .dimmed()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn team(abbrev: &str, division: &str, points: i64) -> Value {
+        json!({
+            "teamAbbrev": { "default": abbrev },
+            "divisionName": division,
+            "conferenceName": "Eastern",
+            "points": points,
+            "gamesRemaining": 2,
+        })
+    }
+
+    // Two games left for everyone, so each team can add at most 4 points
+    fn league() -> Vec<Value> {
+        vec![
+            team("A1", "Atlantic", 100),
+            team("A2", "Atlantic", 90),
+            team("A3", "Atlantic", 80),
+            team("A4", "Atlantic", 79),
+            team("A5", "Atlantic", 58),
+            team("M1", "Metropolitan", 95),
+            team("M2", "Metropolitan", 85),
+            team("M3", "Metropolitan", 70),
+            team("M4", "Metropolitan", 60),
+            team("M5", "Metropolitan", 40),
+        ]
+    }
+
+    fn find<'a>(teams: &'a [Value], abbrev: &str) -> &'a Value {
+        teams.iter().find(|t| team_abbrev(t) == abbrev).unwrap()
+    }
+
+    #[test]
+    fn misses_playoffs_follows_the_wild_card_format() {
+        // One division holds the top six spots, so its 6th team misses
+        assert!(misses_playoffs(5, 0));
+        assert!(!misses_playoffs(2, 10));
+        assert!(!misses_playoffs(3, 4));
+        assert!(misses_playoffs(3, 5));
+        assert!(misses_playoffs(4, 4));
+    }
+
+    #[test]
+    fn clinched_and_eliminated_playoffs() {
+        let teams = league();
+        let groups = |abbrev: &str| {
+            let team = find(&teams, abbrev);
+            (
+                same_group(&teams, team, "divisionName"),
+                rest_of_conference(&teams, team),
+            )
+        };
+
+        // Top 3 in the division
+        let (division, rest) = groups("M3");
+        assert!(clinched_playoffs(70, &division, &rest));
+        // 4th in the division, but no one left can catch it for a wild card
+        let (division, rest) = groups("A4");
+        assert!(clinched_playoffs(79, &division, &rest));
+        // Still racing A5 for the second wild card
+        let (division, rest) = groups("M4");
+        assert!(!clinched_playoffs(60, &division, &rest));
+        assert!(!eliminated_playoffs(64, &division, &rest));
+        let (division, rest) = groups("M5");
+        assert!(eliminated_playoffs(44, &division, &rest));
+    }
+
+    #[test]
+    fn calculate_markers_for_every_kind_of_team() {
+        let markers = calculate_markers(&league());
+        assert_eq!(markers.get("A1"), Some(&'p'));
+        assert_eq!(markers.get("M1"), Some(&'y'));
+        assert_eq!(markers.get("A2"), Some(&'x'));
+        assert_eq!(markers.get("A4"), Some(&'x'));
+        assert_eq!(markers.get("M5"), Some(&'e'));
+        assert_eq!(markers.get("M4"), None);
+        assert_eq!(markers.get("A5"), None);
+    }
+
+    #[test]
+    fn clinch_markers_keep_the_stronger_marker() {
+        let mut teams = league();
+        teams[0]["clinchIndicator"] = json!("x");
+        teams[1]["clinchIndicator"] = json!("y");
+        teams[8]["clinchIndicator"] = json!("e");
+
+        let markers = clinch_markers(&teams);
+        // Calculated Presidents' Trophy beats the league's lagging x
+        assert_eq!(markers.get("A1"), Some(&'p'));
+        // The league's division title beats a calculated x
+        assert_eq!(markers.get("A2"), Some(&'y'));
+        assert_eq!(markers.get("M4"), Some(&'e'));
+    }
+
+    #[test]
+    fn magic_and_tragic_numbers() {
+        let teams = league();
+        let numbers = |abbrev: &str| {
+            let team = find(&teams, abbrev);
+            let division = same_group(&teams, team, "divisionName");
+            let rest = rest_of_conference(&teams, team);
+            (
+                playoff_magic_number(team, &division, &rest),
+                playoff_tragic_number(team, &division, &rest),
+            )
+        };
+
+        // Clinched, so there's nothing left to gain, and eliminated
+        assert_eq!(numbers("A2").0, 0);
+        assert_eq!(numbers("M5").1, Some(0));
+        // M4 clinches at 63 points, when A5 (62 at most) can't pass it, and is out once A5
+        // gets above what M4 can still reach
+        assert_eq!(numbers("M4"), (3, Some(7)));
+
+        // Division title: A1 can still reach 104 ahead of A2's 90
+        let a2 = find(&teams, "A2");
+        let division = same_group(&teams, a2, "divisionName");
+        assert_eq!(magic_number(a2, &division, 1), 15);
+        let m1 = find(&teams, "M1");
+        let division = same_group(&teams, m1, "divisionName");
+        assert_eq!(magic_number(m1, &division, 1), 0);
+    }
+}
@@ -1,12 +1,466 @@
 use colored::Colorize;
-use crate::api::{NHL_API_URL, nhl_api_request};
+use serde_json::{Value, json};
+use crate::api::{
+    NHL_API_URL, PLAYOFFS, REGULAR_SEASON, nhl_api_request, season_id, stats_report_url,
+};
+
+pub struct LeadersOptions {
+    /// Season to show leaders for (e.g. 2019-20), the current season when not given
+    pub season: Option<String>,
+    /// Show playoff leaders instead of the regular season
+    pub playoffs: bool,
+    /// Number of players to show
+    pub limit: usize,
+    /// Only players on this team (e.g. TOR)
+    pub team: Option<String>,
+    /// Only players at this position: F, D, C, LW or RW
+    pub position: Option<String>,
+    /// Only rookies
+    pub rookies: bool,
+    /// Fewest games played to qualify for the rate leaderboards
+    pub min_games: i64,
+}
+
+// Boards on the `all` dashboard: leaders endpoint category and title
+const DASHBOARD_SKATERS: [(&str, &str); 7] = [
+    ("points", "Points"),
+    ("goals", "Goals"),
+    ("assists", "Assists"),
+    ("plusMinus", "Plus Minus"),
+    ("penaltyMins", "Penalty Minutes"),
+    ("toi", "Time On Ice"),
+    ("faceoffLeaders", "Faceoffs"),
+];
+const DASHBOARD_GOALIES: [(&str, &str); 4] = [
+    ("wins", "Wins"),
+    ("savePctg", "Save %"),
+    ("goalsAgainstAverage", "GAA"),
+    ("shutouts", "Shutouts"),
+];
+const DASHBOARD_SIZE: usize = 5;
+const DASHBOARD_COLUMNS: usize = 3;
+const DASHBOARD_WIDTH: usize = 31;
+
+// Leaderboards of per game and per 60 minute rates, worked out from season totals
+const RATE_CATEGORIES: [&str; 4] = [
+    "points-per-game",
+    "goals-per-60",
+    "primary-points-per-60",
+    "shots-per-game",
+];
+
+// One row of a leaderboard
+struct Leader {
+    name: String,
+    team: String,
+    value: f64,
+}
+
+// The stats REST API field for a leaders category
+fn summary_stat(property: &str) -> &'static str {
+    match property {
+        "goals" => "goals",
+        "assists" => "assists",
+        "toi" => "timeOnIcePerGame",
+        "plusMinus" => "plusMinus",
+        "penaltyMins" => "penaltyMinutes",
+        "faceoffLeaders" => "faceoffWinPct",
+        "savePctg" => "savePct",
+        "goalsAgainstAverage" => "goalsAgainstAverage",
+        "shutouts" => "shutouts",
+        "wins" => "wins",
+        _ => "points",
+    }
+}
+
+// Percentage and average categories, where the leaders endpoints only rank players with
+// enough games played
+fn needs_qualifying(property: &str) -> bool {
+    matches!(
+        property,
+        "savePctg" | "goalsAgainstAverage" | "faceoffLeaders"
+    )
+}
+
+// Categories where the lowest value leads
+fn lower_is_better(property: &str) -> bool {
+    property == "goalsAgainstAverage"
+}
+
+fn format_value(property: &str, value: f64) -> String {
+    match property {
+        "savePctg" | "faceoffLeaders" => format!("{:.2}%", value * 100.0),
+        "goalsAgainstAverage" => format!("{:.2}", value),
+        "toi" => format!("{:.2}m", value),
+        "pointsPerGame" | "goalsPer60" | "primaryPointsPer60" | "shotsPerGame" => {
+            format!("{:.2}", value)
+        }
+        _ => format!("{}", value),
+    }
+}
+
+// Rank labels for leaders in order, players with the same value share a rank ("T-3")
+fn rank_labels(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let rank = values.iter().position(|v| v == value).unwrap_or(i) + 1;
+            if values.iter().filter(|v| *v == value).count() > 1 {
+                format!("T-{}", rank)
+            } else {
+                rank.to_string()
+            }
+        })
+        .collect()
+}
+
+// Cayenne filter for a position. Forwards are everyone but defensemen.
+fn position_filter(position: &str) -> Option<&'static str> {
+    match position.to_uppercase().as_str() {
+        "F" => Some("positionCode!=\"D\""),
+        "D" => Some("positionCode=\"D\""),
+        "C" => Some("positionCode=\"C\""),
+        "LW" => Some("positionCode=\"L\""),
+        "RW" => Some("positionCode=\"R\""),
+        _ => None,
+    }
+}
+
+fn on_team(player: &Value, team: &str) -> bool {
+    // Players traded during the season list every team they played for
+    player["teamAbbrevs"]
+        .as_str()
+        .unwrap_or("")
+        .split(',')
+        .any(|abbrev| abbrev.trim().eq_ignore_ascii_case(team))
+}
+
+// Stats REST API url for a report (e.g. skater/summary) of one season and game type, with the
+// position and rookie filters and, when given, a minimum games played
+fn report_url(
+    report: &str,
+    season_id: &str,
+    options: &LeadersOptions,
+    min_games: Option<i64>,
+    sort: &str,
+    limit: i64,
+) -> Result<reqwest::Url, Box<dyn std::error::Error>> {
+    let game_type = if options.playoffs {
+        PLAYOFFS
+    } else {
+        REGULAR_SEASON
+    };
+
+    let mut filters = vec![
+        format!("seasonId={}", season_id),
+        format!("gameTypeId={}", game_type),
+    ];
+    if let Some(filter) = options.position.as_deref().and_then(position_filter) {
+        filters.push(filter.to_string());
+    }
+    if options.rookies {
+        filters.push("isRookie=\"1\"".to_string());
+    }
+    if let Some(min_games) = min_games {
+        filters.push(format!("gamesPlayed>={}", min_games));
+    }
+
+    stats_report_url(report, &filters.join(" and "), Some(sort), 0, limit)
+}
+
+// Per game and per 60 minute rates for every skater who played at least the minimum games.
+// Primary points (goals and first assists) are only reported at 5 on 5, as the per 60 rates
+// of the scoring rates report. Skaters missing a stat are left out rather than shown as 0.
+async fn fetch_rate_leaders(
+    client: &reqwest::Client,
+    property: &str,
+    season_id: &str,
+    options: &LeadersOptions,
+) -> Result<Vec<Leader>, Box<dyn std::error::Error>> {
+    let report = if property == "primaryPointsPer60" {
+        "skater/scoringRates"
+    } else {
+        "skater/summary"
+    };
+    let sort = json!([{ "property": "gamesPlayed", "direction": "DESC" }]).to_string();
+    let url = report_url(report, season_id, options, None, &sort, -1)?;
+    let response = nhl_api_request(client, url.as_str()).await?;
+
+    Ok(response["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|player| player["gamesPlayed"].as_i64().unwrap_or(0) >= options.min_games.max(1))
+        .filter(|player| {
+            options
+                .team
+                .as_deref()
+                .is_none_or(|team| on_team(player, team))
+        })
+        .filter_map(|player| {
+            let stat = |key: &str| player[key].as_f64();
+            let games = stat("gamesPlayed")?;
+            let value = match property {
+                "goalsPer60" => {
+                    let seconds = stat("timeOnIcePerGame")? * games;
+                    if seconds > 0.0 {
+                        stat("goals")? * 3600.0 / seconds
+                    } else {
+                        0.0
+                    }
+                }
+                "primaryPointsPer60" => stat("goalsPer605v5")? + stat("primaryAssistsPer605v5")?,
+                "shotsPerGame" => stat("shots")? / games,
+                _ => stat("points")? / games,
+            };
+            Some(Leader {
+                name: player["skaterFullName"].as_str().unwrap_or("").to_string(),
+                team: player["teamAbbrevs"].as_str().unwrap_or("---").to_string(),
+                value,
+            })
+        })
+        .collect())
+}
+
+// One top 5 list of the dashboard as lines padded to the board width
+fn dashboard_board(title: &str, property: &str, response: &Value) -> Vec<String> {
+    let mut players = response[property].as_array().cloned().unwrap_or_default();
+    let value = |player: &Value| player["value"].as_f64().unwrap_or(0.0);
+    players.sort_by(|a, b| {
+        if lower_is_better(property) {
+            value(a).total_cmp(&value(b))
+        } else {
+            value(b).total_cmp(&value(a))
+        }
+    });
+    let values: Vec<String> = players
+        .iter()
+        .map(|player| format_value(property, value(player)))
+        .collect();
+    let ranks = rank_labels(&values);
+
+    let mut lines = vec![format!("{:<width$}", title, width = DASHBOARD_WIDTH)
+        .bold()
+        .to_string()];
+    for ((player, value), rank) in players.iter().zip(&values).zip(&ranks).take(DASHBOARD_SIZE) {
+        let name = format!(
+            "{}. {}",
+            player["firstName"]["default"]
+                .as_str()
+                .and_then(|name| name.chars().next())
+                .unwrap_or(' '),
+            player["lastName"]["default"].as_str().unwrap_or("")
+        );
+        lines.push(format!(
+            "{:<4} {:<15} {:<3} {:>6}",
+            rank,
+            name.chars().take(15).collect::<String>(),
+            player["teamAbbrev"].as_str().unwrap_or("---"),
+            value.green().bold()
+        ));
+    }
+    lines
+}
+
+// Top 5 in every skater and goalie category, with both leaders endpoints fetched at once
+async fn display_dashboard(
+    client: &reqwest::Client,
+    player_url: &str,
+    goalie_url: &str,
+    subtitle: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    // One extra player shows whether the last one on each board is tied
+    let query = |categories: &[(&str, &str)]| {
+        let names: Vec<&str> = categories.iter().map(|(property, _)| *property).collect();
+        format!(
+            "?categories={}&limit={}",
+            names.join(","),
+            DASHBOARD_SIZE + 1
+        )
+    };
+    let skater_url = format!("{}{}", player_url, query(&DASHBOARD_SKATERS));
+    let goalie_url = format!("{}{}", goalie_url, query(&DASHBOARD_GOALIES));
+    let (skaters, goalies) = tokio::join!(
+        nhl_api_request(client, &skater_url),
+        nhl_api_request(client, &goalie_url)
+    );
+    let (skaters, goalies) = (skaters?, goalies?);
+
+    let boards: Vec<Vec<String>> = DASHBOARD_SKATERS
+        .iter()
+        .map(|(property, title)| dashboard_board(title, property, &skaters))
+        .chain(
+            DASHBOARD_GOALIES
+                .iter()
+                .map(|(property, title)| dashboard_board(title, property, &goalies)),
+        )
+        .collect();
+
+    let width = DASHBOARD_COLUMNS * DASHBOARD_WIDTH + (DASHBOARD_COLUMNS - 1) * 3;
+    let separator = "-".repeat(width);
+    println!("\n{}", separator);
+    println!("{:^width$}", "NHL LEADERS".bold());
+    if !subtitle.is_empty() {
+        println!("{:^width$}", subtitle.join(", "));
+    }
+    println!("{}", separator);
+
+    for row in boards.chunks(DASHBOARD_COLUMNS) {
+        let height = row.iter().map(|board| board.len()).max().unwrap_or(0);
+        for line in 0..height {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|board| {
+                    board
+                        .get(line)
+                        .cloned()
+                        .unwrap_or_else(|| " ".repeat(DASHBOARD_WIDTH))
+                })
+                .collect();
+            println!("{}", cells.join("   "));
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+// Leaders from the stats REST API, which can filter by position and rookies as well
+async fn fetch_summary_leaders(
+    client: &reqwest::Client,
+    goalies: bool,
+    property: &str,
+    season_id: &str,
+    options: &LeadersOptions,
+) -> Result<Vec<Leader>, Box<dyn std::error::Error>> {
+    let stat = summary_stat(property);
+    let sort = json!([{
+        "property": stat,
+        "direction": if lower_is_better(property) { "ASC" } else { "DESC" },
+    }])
+    .to_string();
+    // The team filter is applied here, so every player is needed to fill the list. Otherwise
+    // one extra player shows whether the last one on the list is tied.
+    let limit = if options.team.is_some() {
+        -1
+    } else {
+        options.limit as i64 + 1
+    };
+
+    let report = if goalies {
+        "goalie/summary"
+    } else {
+        "skater/summary"
+    };
+    // Without a minimum, a backup with one perfect game would lead save %
+    let min_games = needs_qualifying(property).then_some(options.min_games.max(1));
+    let url = report_url(report, season_id, options, min_games, &sort, limit)?;
+    let response = nhl_api_request(client, url.as_str()).await?;
+
+    let name_key = if goalies {
+        "goalieFullName"
+    } else {
+        "skaterFullName"
+    };
+    Ok(response["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|player| {
+            options
+                .team
+                .as_deref()
+                .is_none_or(|team| on_team(player, team))
+        })
+        .take(options.limit + 1)
+        .map(|player| {
+            let value = player[stat].as_f64().unwrap_or(0.0);
+            Leader {
+                name: player[name_key].as_str().unwrap_or("").to_string(),
+                team: player["teamAbbrevs"].as_str().unwrap_or("---").to_string(),
+                // Time on ice comes in seconds, the leaders endpoint uses minutes
+                value: if stat == "timeOnIcePerGame" {
+                    value / 60.0
+                } else {
+                    value
+                },
+            }
+        })
+        .collect())
+}
 
 pub async fn display_leaders(
     client: &reqwest::Client,
     category: &str,
+    options: &LeadersOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let player_url = format!("{}/skater-stats-leaders/current", NHL_API_URL);
-    let goalie_url = format!("{}/goalie-stats-leaders/current", NHL_API_URL);
+    if let Some(position) = &options.position {
+        if position_filter(position).is_none() {
+            println!("Invalid position. Use F, D, C, LW, or RW");
+            return Ok(());
+        }
+    }
+
+    // The leaders endpoints can't filter, the stats REST API is used when there are filters
+    let filtered = options.team.is_some() || options.position.is_some() || options.rookies;
+    // Rates aren't on the leaders endpoints at all
+    let rate = RATE_CATEGORIES.contains(&category.to_lowercase().as_str());
+    let season_id = if options.season.is_some() || options.playoffs || filtered || rate {
+        Some(season_id(client, options.season.as_deref()).await?)
+    } else {
+        None
+    };
+
+    let mut subtitle = Vec::new();
+    if let (Some(season_id), true) = (&season_id, options.season.is_some() || options.playoffs) {
+        let label = if options.playoffs {
+            "Playoffs"
+        } else {
+            "Regular Season"
+        };
+        subtitle.push(format!(
+            "{}-{} {}",
+            &season_id[0..4],
+            &season_id[6..8],
+            label
+        ));
+    }
+    if let Some(team) = &options.team {
+        subtitle.push(team.to_uppercase());
+    }
+    if let Some(position) = &options.position {
+        subtitle.push(position.to_uppercase());
+    }
+    if options.rookies {
+        subtitle.push("Rookies".to_string());
+    }
+    if rate {
+        subtitle.push(format!("Min {} GP", options.min_games));
+    }
+
+    // The current regular season has a shortcut, anything else needs a season id and game type
+    let path = match &season_id {
+        Some(season_id) => {
+            let game_type = if options.playoffs {
+                PLAYOFFS
+            } else {
+                REGULAR_SEASON
+            };
+            format!("{}/{}", season_id, game_type)
+        }
+        None => "current".to_string(),
+    };
+    let player_url = format!("{}/skater-stats-leaders/{}", NHL_API_URL, path);
+    let goalie_url = format!("{}/goalie-stats-leaders/{}", NHL_API_URL, path);
+
+    if category.eq_ignore_ascii_case("all") {
+        if filtered {
+            println!("The leaders dashboard can't be filtered by team, position, or rookies");
+            return Ok(());
+        }
+        return display_dashboard(client, &player_url, &goalie_url, &subtitle).await;
+    }
 
     let separator = "-".repeat(60);
     // Title is the title of the leaderboard
@@ -62,48 +516,118 @@ pub async fn display_leaders(
             goalie_url,
         ),
         "wins" => ("Goalie Wins Leaders", "wins", "Wins", goalie_url),
+        // Rates
+        "points-per-game" => (
+            "Player Points Per Game Leaders",
+            "pointsPerGame",
+            "P/GP",
+            player_url,
+        ),
+        "goals-per-60" => (
+            "Player Goals Per 60 Leaders",
+            "goalsPer60",
+            "G/60",
+            player_url,
+        ),
+        "primary-points-per-60" => (
+            "Player 5v5 Primary Points Per 60 Leaders",
+            "primaryPointsPer60",
+            "P1/60",
+            player_url,
+        ),
+        "shots-per-game" => (
+            "Player Shots Per Game Leaders",
+            "shotsPerGame",
+            "S/GP",
+            player_url,
+        ),
         _ => {
-            println!("Invalid category. Use 'points', 'goals', or 'assists'");
+            println!(
+                "Invalid category. Use one of: points, goals, assists, toi, plus-minus, \
+                 penalty-minutes, faceoffs, save-percentage, goals-against-avg, shutouts, wins, \
+                 points-per-game, goals-per-60, primary-points-per-60, shots-per-game, or all"
+            );
             return Ok(());
         }
     };
 
-    let leaders = nhl_api_request(client, &api_url).await?;
+    let goalies = matches!(
+        property,
+        "savePctg" | "goalsAgainstAverage" | "shutouts" | "wins"
+    );
+    if goalies && options.position.is_some() {
+        println!("Position filters only apply to skaters");
+        return Ok(());
+    }
+
+    let mut leaders = match (&season_id, filtered) {
+        (Some(season_id), _) if rate => {
+            fetch_rate_leaders(client, property, season_id, options).await?
+        }
+        (Some(season_id), true) => {
+            fetch_summary_leaders(client, goalies, property, season_id, options).await?
+        }
+        _ => {
+            let url = format!(
+                "{}?categories={}&limit={}",
+                api_url,
+                property,
+                options.limit + 1
+            );
+            let response = nhl_api_request(client, &url).await?;
+
+            response[property]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|player| Leader {
+                    name: format!(
+                        "{} {}",
+                        player["firstName"]["default"].as_str().unwrap_or(""),
+                        player["lastName"]["default"].as_str().unwrap_or("")
+                    ),
+                    team: player["teamName"]["default"]
+                        .as_str()
+                        .unwrap_or("---")
+                        .to_string(),
+                    value: player["value"].as_f64().unwrap_or(0.0),
+                })
+                .collect()
+        }
+    };
 
     println!("\n{}", separator);
     println!("{:^60}", title.bold());
+    if !subtitle.is_empty() {
+        println!("{:^60}", subtitle.join(", "));
+    }
     println!("{}", separator);
-    println!("{:<4} {:<25} {:<20} {:>8}", "Rank", "Player", "Team", label);
+    println!("{:<5} {:<24} {:<20} {:>8}", "Rank", "Player", "Team", label);
     println!("{}", separator);
 
-    if let Some(players) = leaders[property].as_array() {
-        let mut players = players.to_vec();
-        players.sort_by_key(|p| -(p[property].as_i64().unwrap_or(0)));
-
-        for (i, player) in players.iter().take(20).enumerate() {
-            let first_name = player["firstName"]["default"].as_str().unwrap_or("");
-            let last_name = player["lastName"]["default"].as_str().unwrap_or("");
-            let team_name = player["teamName"]["default"].as_str().unwrap_or("---");
-            let value = player["value"].as_f64().unwrap_or(0.0);
-
-            let player_name = format!("{} {}", first_name, last_name);
-            let mut value_formatted = format!("{}", value);
-            if property == "savePctg" {
-                value_formatted = format!("{:.2}%", value * 100.0);
-            }
-            if property == "toi" {
-                value_formatted = format!("{:.2}m", value);
-            }
-
-            println!(
-                "{:<4} {:<25} {:<20} {:>8}",
-                (i + 1),
-                player_name,
-                team_name,
-                value_formatted.to_string().green().bold()
-            );
+    leaders.sort_by(|a, b| {
+        if lower_is_better(property) {
+            a.value.total_cmp(&b.value)
+        } else {
+            b.value.total_cmp(&a.value)
         }
+    });
+    // Ties are decided on the value as shown
+    let values: Vec<String> = leaders
+        .iter()
+        .map(|leader| format_value(property, leader.value))
+        .collect();
+    let ranks = rank_labels(&values);
+
+    for ((leader, value), rank) in leaders.iter().zip(&values).zip(&ranks).take(options.limit) {
+        println!(
+            "{:<5} {:<24} {:<20} {:>8}",
+            rank,
+            leader.name,
+            leader.team,
+            value.green().bold()
+        );
     }
 
     Ok(())
-} 
\ No newline at end of file
+}
@@ -0,0 +1,143 @@
+use colored::Colorize;
+use serde_json::Value;
+use crate::standings::{WILD_CARD_SPOTS, wildcard_standings};
+use crate::tiebreakers::Tiebreaks;
+
+/// Chance (%) of each non-playoff team winning a lottery draw, from the worst team up, under
+/// the lottery rules used since 2022
+pub const LOTTERY_ODDS: [f64; 16] = [
+    18.5, 13.5, 11.5, 9.5, 8.5, 7.5, 6.5, 6.0, 5.0, 3.5, 3.0, 2.5, 2.0, 1.5, 0.5, 0.5,
+];
+
+// There are two draws, for the first and second picks, and a team that wins one can move up
+// at most 10 spots in the draft order
+const DRAWS: usize = 2;
+const MAX_JUMP: usize = 10;
+
+/// Parse a comma separated table of lottery odds in percent, worst team first
+pub fn parse_lottery_odds(spec: &str) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    spec.split(',')
+        .map(|odds| {
+            odds.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|odds| *odds >= 0.0)
+                .ok_or_else(|| format!("Invalid lottery odds '{}'", odds.trim()).into())
+        })
+        .collect()
+}
+
+// Move a draw winner up to the pick it won, or as close as it can get. When it can't reach the
+// pick, the team left in that spot keeps it.
+fn apply_draw(order: &mut Vec<usize>, winner: usize, pick: usize) {
+    let Some(position) = order.iter().position(|team| *team == winner) else {
+        return;
+    };
+    let target = pick.max(position.saturating_sub(MAX_JUMP));
+    if target < position {
+        order.remove(position);
+        order.insert(target, winner);
+    }
+}
+
+// Chance of every team ending up with each pick, going through every possible pair of draw
+// winners. A team can only win one draw, so the second draw is between the other teams.
+fn pick_odds(odds: &[f64]) -> Vec<Vec<f64>> {
+    let teams = odds.len();
+    let total: f64 = odds.iter().sum();
+    let mut picks = vec![vec![0.0; teams]; teams];
+    if teams < DRAWS || total <= 0.0 {
+        for (team, team_picks) in picks.iter_mut().enumerate() {
+            team_picks[team] = 1.0;
+        }
+        return picks;
+    }
+    let chance: Vec<f64> = odds.iter().map(|odds| odds / total).collect();
+
+    for first in 0..teams {
+        let remaining = 1.0 - chance[first];
+        for second in (0..teams).filter(|team| *team != first) {
+            let probability = if remaining > 0.0 {
+                chance[first] * chance[second] / remaining
+            } else {
+                0.0
+            };
+            if probability == 0.0 {
+                continue;
+            }
+
+            let mut order: Vec<usize> = (0..teams).collect();
+            apply_draw(&mut order, first, 0);
+            apply_draw(&mut order, second, 1);
+            for (pick, team) in order.into_iter().enumerate() {
+                picks[team][pick] += probability;
+            }
+        }
+    }
+
+    picks
+}
+
+fn format_percent(probability: f64) -> String {
+    if probability <= 0.0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", probability * 100.0)
+    }
+}
+
+/// Non-playoff teams in draft lottery order, worst team first, with their odds of winning a
+/// draw and of ending up with the first pick, a top 2 pick, and their lowest possible pick
+pub fn print_lottery(teams: &[Value], tiebreaks: &Tiebreaks, odds: &[f64]) {
+    let mut lottery: Vec<&Value> = wildcard_standings(teams, tiebreaks)
+        .into_iter()
+        .flat_map(|conference| conference.wild_card.into_iter().skip(WILD_CARD_SPOTS))
+        .collect();
+    // Lottery order is the reverse of the standings, tiebreakers included
+    tiebreaks.sort(&mut lottery);
+    lottery.reverse();
+
+    let odds: Vec<f64> = (0..lottery.len())
+        .map(|i| odds.get(i).copied().unwrap_or(0.0))
+        .collect();
+    let picks = pick_odds(&odds);
+
+    let header = format!(
+        "{:>4} {:<24} {:>3} {:>3} {:>5} {:>7} {:>7} {:>7} {:>4}",
+        "", "Team", "GP", "PTS", "P%", "DRAW", "1ST", "TOP 2", "LOW"
+    );
+    let width = header.len();
+    let separator = "-".repeat(width);
+
+    println!("\n{}", separator);
+    println!("{:^width$}", "DRAFT LOTTERY".bold());
+    println!("{}", separator);
+    println!("{}", header.bold().underline());
+
+    for (i, team) in lottery.iter().enumerate() {
+        let team_picks = &picks[i];
+        let lowest = team_picks
+            .iter()
+            .rposition(|probability| *probability > 0.0)
+            .unwrap_or(i);
+        println!(
+            "{:>4} {:<24} {:>3} {:>3} {:>5.3} {:>7} {:>7} {:>7} {:>4}",
+            format!("{}.", i + 1),
+            team["teamName"]["default"].as_str().unwrap_or("Unknown"),
+            team["gamesPlayed"].as_i64().unwrap_or(0),
+            team["points"].as_i64().unwrap_or(0),
+            team["pointPctg"].as_f64().unwrap_or(0.0),
+            format_percent(odds[i] / 100.0),
+            format_percent(team_picks[0]).bold(),
+            format_percent(team_picks.iter().take(DRAWS).sum()),
+            lowest + 1
+        );
+    }
+
+    println!(
+        "\n{}",
+        "DRAW: odds of winning a lottery draw. 1ST / TOP 2: odds of ending up with the first / a \
+         top 2 pick, since a draw winner can move up at most 10 spots."
+            .dimmed()
+    );
+}
@@ -1,9 +1,20 @@
 mod api;
 mod boxscores;
+mod clinch;
 mod leaders;
+mod lottery;
+mod milestone;
+mod odds;
+mod pbp;
+mod playoffs;
 mod scores;
+mod series;
+mod shifts;
+mod shotmap;
 mod standings;
-mod ovi;
+mod stats;
+mod tiebreakers;
+mod timeline;
 
 use clap::{Parser, Subcommand};
 
@@ -21,21 +32,208 @@ enum Commands {
     /// Get current NHL standings
     Standings {
         #[arg(default_value = "wildcard")]
-        /// Type of standings (division, conference, wildcard, league)
+        /// Type of standings (wildcard, division, conference, league, diff, magic, projected,
+        /// lottery)
         format: String,
+        /// Show the standings as of this date (YYYY-MM-DD)
+        #[arg(long, conflicts_with = "season")]
+        date: Option<String>,
+        /// Show the final standings of a season (e.g. 2022-23)
+        #[arg(long)]
+        season: Option<String>,
+        /// Start date for the `diff` format (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+        /// End date for the `diff` format (YYYY-MM-DD), defaults to today
+        #[arg(long)]
+        to: Option<String>,
+        /// Columns to show: a preset (basic, full, playoff) or a comma separated list
+        /// (gp, w, l, otl, pts, pct, rw, row, gf, ga, diff, home, road, l10, strk, so, pace)
+        #[arg(long, default_value = "basic")]
+        columns: String,
+        /// Sort by a column instead of standings order (not applied to the wildcard view)
+        #[arg(long)]
+        sort: Option<String>,
+        /// Sort ascending instead of descending
+        #[arg(long, requires = "sort")]
+        asc: bool,
+        /// Show which tiebreaker separated teams tied on points
+        #[arg(long)]
+        explain_ties: bool,
+        /// How the `projected` format extrapolates the rest of the season: pace (current
+        /// points %) or schedule (expected points against each remaining opponent)
+        #[arg(long, default_value = "pace")]
+        projection: String,
+        /// Odds (%) of winning a draft lottery draw for the `lottery` format, comma separated
+        /// from the worst team up (defaults to the current lottery table)
+        #[arg(long)]
+        lottery_odds: Option<String>,
     },
     /// Get NHL scoring leaders
     Leaders {
         /// Type of leaders
         /// (Players: points, goals, assists, penalty-minutes, toi, plus-minus, faceoffs)
         /// (Goalies: save-percentage, goals-against-avg, shutouts, wins)
+        /// (Rates: points-per-game, goals-per-60, primary-points-per-60, shots-per-game)
+        /// (all: top 5 in every skater and goalie category)
         category: String,
+        /// Show leaders for a past season (e.g. 2019-20)
+        #[arg(long)]
+        season: Option<String>,
+        /// Show playoff leaders instead of the regular season
+        #[arg(long)]
+        playoffs: bool,
+        /// Number of players to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Only players on this team (e.g. TOR)
+        #[arg(long)]
+        team: Option<String>,
+        /// Only players at this position (F, D, C, LW, RW)
+        #[arg(long)]
+        position: Option<String>,
+        /// Only rookies
+        #[arg(long)]
+        rookies: bool,
+        /// Fewest games played to qualify for the rate leaderboards, and for save %, GAA
+        /// and faceoffs when filtering by team, position or rookies
+        #[arg(long, default_value_t = 10)]
+        min_games: i64,
     },
     /// Get detailed boxscore for a specific game
     Boxscores,
+    /// Get the play-by-play for a specific game
+    Pbp {
+        /// Game id (e.g. 2023020204)
+        game: String,
+        /// Only show these event types, comma separated
+        /// (faceoff, shot, miss, block, hit, giveaway, takeaway, penalty, goal, stoppage)
+        #[arg(long = "type")]
+        event_type: Option<String>,
+        /// Only show events by this team (abbreviation, e.g. WSH)
+        #[arg(long)]
+        team: Option<String>,
+        /// Only show events involving this player (name or part of a name)
+        #[arg(long)]
+        player: Option<String>,
+        /// Only show events in this period (4 for OT)
+        #[arg(long)]
+        period: Option<i64>,
+    },
+    /// Draw a shot map for a specific game
+    Shotmap {
+        /// Game id (e.g. 2023020204)
+        game: String,
+        /// Only show shots from this period (4 for OT)
+        #[arg(long)]
+        period: Option<i64>,
+    },
+    /// Draw a shift chart and line combinations for a specific game
+    Shifts {
+        /// Game id (e.g. 2023020204)
+        game: String,
+        /// Only show this team (abbreviation, e.g. WSH)
+        #[arg(long)]
+        team: Option<String>,
+    },
+    /// Draw a shot attempt timeline with goals and penalties for a specific game
+    Timeline {
+        /// Game id (e.g. 2023020204)
+        game: String,
+    },
+    /// Simulate the rest of the regular season to get playoff odds
+    Odds {
+        /// Number of seasons to simulate
+        #[arg(
+            long,
+            default_value_t = 10000,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        simulations: usize,
+        /// Seed for the simulations, the same seed always gives the same odds
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Per-game win model: pct (log5 on points %) or even (coin flip)
+        #[arg(long, default_value = "pct")]
+        model: String,
+        /// Added to the home team's chance of winning each game (0 to 1)
+        #[arg(long, default_value_t = 0.04, value_parser = parse_probability)]
+        home_edge: f64,
+        /// Chance that a game goes to overtime or a shootout (0 to 1)
+        #[arg(long, default_value_t = 0.23, value_parser = parse_probability)]
+        ot_rate: f64,
+    },
+    /// Show the playoff bracket, or the would-be matchups before the playoffs start
+    Playoffs {
+        /// Show the bracket of a past season (e.g. 2022-23)
+        #[arg(long)]
+        season: Option<String>,
+    },
+    /// Show a team's playoff series game by game
+    Series {
+        /// Team abbreviation (e.g. NYR)
+        team: String,
+        /// Show the team's series from a past season (e.g. 2022-23)
+        #[arg(long)]
+        season: Option<String>,
+    },
+    /// Query any report of the NHL stats API, with filters, sorting and field selection
+    Stats {
+        /// What to query: skaters, goalies or teams
+        kind: String,
+        /// Stats API report (e.g. summary, realtime, faceoffpercentages, powerplay)
+        #[arg(long, default_value = "summary")]
+        report: String,
+        /// Query a past season (e.g. 2019-20)
+        #[arg(long)]
+        season: Option<String>,
+        /// Query the playoffs instead of the regular season
+        #[arg(long)]
+        playoffs: bool,
+        /// Cayenne filter expression, can be repeated (e.g. 'goals>=30', 'positionCode="D"')
+        #[arg(long = "filter")]
+        filters: Vec<String>,
+        /// Fields to sort by, comma separated (defaults to points, or wins for goalies)
+        #[arg(long)]
+        sort: Option<String>,
+        /// Sort ascending instead of descending
+        #[arg(long, requires = "sort")]
+        asc: bool,
+        /// Fields to show, comma separated
+        #[arg(long)]
+        fields: Option<String>,
+        /// Number of rows to show, 0 for every row
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Show a player's progress towards a career milestone in any counting stat
+    Milestone {
+        /// Player name or id (e.g. "Connor McDavid" or 8478402)
+        player: String,
+        /// Career stat (e.g. goals, assists, points, gamesPlayed, powerPlayGoals, wins)
+        stat: String,
+        /// Milestone to reach (e.g. 1000)
+        target: i64,
+        /// Project the date the milestone is reached at the player's pace this season
+        #[arg(long)]
+        project: bool,
+    },
+    /// Show Ovi's goals against Gretzky's record
     Ovi
 }
 
+// A probability argument, between 0 and 1
+fn parse_probability(value: &str) -> Result<f64, String> {
+    let probability: f64 = value
+        .parse()
+        .map_err(|_| format!("'{}' isn't a number", value))?;
+    if (0.0..=1.0).contains(&probability) {
+        Ok(probability)
+    } else {
+        Err(format!("{} isn't between 0 and 1", probability))
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
@@ -45,17 +243,140 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Scores => {
             scores::display_scores(&client).await?;
         }
-        Commands::Standings { format } => {
-            standings::display_standings(&client, &format).await?;
+        Commands::Standings {
+            format,
+            date,
+            season,
+            from,
+            to,
+            columns,
+            sort,
+            asc,
+            explain_ties,
+            projection,
+            lottery_odds,
+        } => {
+            let options = standings::StandingsOptions {
+                date,
+                season,
+                from,
+                to,
+                columns,
+                sort,
+                ascending: asc,
+                explain_ties,
+                projection,
+                lottery_odds,
+            };
+            standings::display_standings(&client, &format, &options).await?;
         }
-        Commands::Leaders { category } => {
-            leaders::display_leaders(&client, &category).await?;
+        Commands::Leaders {
+            category,
+            season,
+            playoffs,
+            limit,
+            team,
+            position,
+            rookies,
+            min_games,
+        } => {
+            let options = leaders::LeadersOptions {
+                season,
+                playoffs,
+                limit,
+                team,
+                position,
+                rookies,
+                min_games,
+            };
+            leaders::display_leaders(&client, &category, &options).await?;
         }
         Commands::Boxscores => {
             boxscores::get_list_of_games_for_boxscores(&client).await?;
         }
+        Commands::Pbp {
+            game,
+            event_type,
+            team,
+            player,
+            period,
+        } => {
+            pbp::display_pbp(
+                &client,
+                &game,
+                event_type.as_deref(),
+                team.as_deref(),
+                player.as_deref(),
+                period,
+            )
+            .await?;
+        }
+        Commands::Shotmap { game, period } => {
+            shotmap::display_shotmap(&client, &game, period).await?;
+        }
+        Commands::Shifts { game, team } => {
+            shifts::display_shifts(&client, &game, team.as_deref()).await?;
+        }
+        Commands::Timeline { game } => {
+            timeline::display_timeline(&client, &game).await?;
+        }
+        Commands::Odds {
+            simulations,
+            seed,
+            model,
+            home_edge,
+            ot_rate,
+        } => {
+            let options = odds::OddsOptions {
+                simulations,
+                seed,
+                model,
+                home_edge,
+                ot_rate,
+            };
+            odds::display_odds(&client, &options).await?;
+        }
+        Commands::Playoffs { season } => {
+            playoffs::display_playoffs(&client, season.as_deref()).await?;
+        }
+        Commands::Series { team, season } => {
+            series::display_series(&client, &team, season.as_deref()).await?;
+        }
+        Commands::Stats {
+            kind,
+            report,
+            season,
+            playoffs,
+            filters,
+            sort,
+            asc,
+            fields,
+            limit,
+        } => {
+            let options = stats::StatsOptions {
+                report,
+                season,
+                playoffs,
+                filters,
+                sort,
+                ascending: asc,
+                fields,
+                limit,
+            };
+            stats::display_stats(&client, &kind, &options).await?;
+        }
+        Commands::Milestone {
+            player,
+            stat,
+            target,
+            project,
+        } => {
+            milestone::display_milestone(&client, &player, &stat, target, project).await?;
+        }
         Commands::Ovi => {
-            ovi::display_ovi(&client).await?;
+            let target = milestone::GRETZKY_GOALS + 1;
+            milestone::display_milestone(&client, milestone::OVECHKIN, "goals", target, true)
+                .await?;
         }
     }
 
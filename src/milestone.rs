@@ -0,0 +1,238 @@
+use chrono::NaiveDate;
+use colored::Colorize;
+use dialoguer::{Select, theme::ColorfulTheme};
+use serde_json::Value;
+use crate::api::{NHL_API_URL, NHL_SEARCH_API_URL, nhl_api_request};
+
+const SEARCH_LIMIT: usize = 20;
+
+/// Alex Ovechkin's player id, and the goal record he set out to break
+pub const OVECHKIN: &str = "8471214";
+pub const GRETZKY_GOALS: i64 = 894;
+
+// Player id for a numeric id or a name. A name is looked up with the player search, asking
+// which player was meant when several match and none has exactly that name.
+async fn find_player(
+    client: &reqwest::Client,
+    player: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if !player.is_empty() && player.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(Some(player.to_string()));
+    }
+
+    let url = reqwest::Url::parse_with_params(
+        &format!("{}/player", NHL_SEARCH_API_URL),
+        &[
+            ("culture", "en-us"),
+            ("limit", &SEARCH_LIMIT.to_string()),
+            ("q", player),
+        ],
+    )?;
+    let results = nhl_api_request(client, url.as_str()).await?;
+    let results: Vec<&Value> = results.as_array().into_iter().flatten().collect();
+    let player_id = |result: &Value| result["playerId"].as_str().map(str::to_string);
+
+    let exact: Vec<&&Value> = results
+        .iter()
+        .filter(|result| {
+            result["name"]
+                .as_str()
+                .is_some_and(|name| name.eq_ignore_ascii_case(player))
+        })
+        .collect();
+    if let [result] = exact.as_slice() {
+        return Ok(player_id(result));
+    }
+
+    match results.as_slice() {
+        [] => Ok(None),
+        [result] => Ok(player_id(result)),
+        _ => {
+            let items: Vec<String> = results
+                .iter()
+                .map(|result| {
+                    format!(
+                        "{} ({}, {})",
+                        result["name"].as_str().unwrap_or("Unknown"),
+                        result["positionCode"].as_str().unwrap_or("-"),
+                        result["teamAbbrev"].as_str().unwrap_or("retired")
+                    )
+                })
+                .collect();
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a player")
+                .items(&items)
+                .default(0)
+                .interact()?;
+            Ok(player_id(results[selection]))
+        }
+    }
+}
+
+// Key of a career stat, matched ignoring case, dashes and underscores so that
+// "power-play-goals" finds powerPlayGoals
+fn stat_key(totals: &Value, stat: &str) -> Option<String> {
+    let normalize = |key: &str| -> String {
+        key.chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let wanted = normalize(stat);
+    totals
+        .as_object()?
+        .keys()
+        .find(|key| normalize(key) == wanted)
+        .cloned()
+}
+
+// Career stats that count up to a milestone
+fn counting_stats(totals: &Value) -> Vec<String> {
+    totals
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, value)| value.is_i64())
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+// Date of the game in which a player on `pace` per game adds `remaining` more, counting
+// only the team's regular season games still to be played
+async fn project_date(
+    client: &reqwest::Client,
+    team: &str,
+    pace: f64,
+    remaining: i64,
+) -> Result<Result<NaiveDate, (usize, usize)>, Box<dyn std::error::Error>> {
+    let games_needed = (remaining as f64 / pace).ceil() as usize;
+
+    let url = format!("{}/club-schedule-season/{}/now", NHL_API_URL, team);
+    let schedule = nhl_api_request(client, &url).await?;
+    let upcoming: Vec<&Value> = schedule["games"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|game| {
+            game["gameType"].as_i64() == Some(2)
+                && matches!(game["gameState"].as_str(), Some("FUT") | Some("PRE"))
+        })
+        .collect();
+
+    let date = upcoming
+        .get(games_needed.saturating_sub(1))
+        .and_then(|game| game["gameDate"].as_str())
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+    Ok(date.ok_or((games_needed, upcoming.len())))
+}
+
+pub async fn display_milestone(
+    client: &reqwest::Client,
+    player: &str,
+    stat: &str,
+    target: i64,
+    project: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(player_id) = find_player(client, player).await? else {
+        println!("No player found matching '{}'", player);
+        return Ok(());
+    };
+    let url = format!("{}/player/{}/landing", NHL_API_URL, player_id);
+    let landing = nhl_api_request(client, &url).await?;
+    let totals = &landing["careerTotals"]["regularSeason"];
+    if !totals.is_object() {
+        println!("No career stats found for player {}", player_id);
+        return Ok(());
+    }
+
+    let Some(key) = stat_key(totals, stat).filter(|key| totals[key].is_i64()) else {
+        println!(
+            "Invalid stat '{}'. Use one of: {}",
+            stat,
+            counting_stats(totals).join(", ")
+        );
+        return Ok(());
+    };
+
+    let name = format!(
+        "{} {}",
+        landing["firstName"]["default"].as_str().unwrap_or(""),
+        landing["lastName"]["default"].as_str().unwrap_or("")
+    );
+    let career = totals[&key].as_i64().unwrap_or(0);
+    let games = totals["gamesPlayed"].as_i64().unwrap_or(0);
+
+    let separator = "=".repeat(70);
+    println!("\n{}", separator);
+    println!("{:^70}", format!("{}: {} {}", name, target, key).bold());
+    println!("{}", separator);
+    println!(
+        "Career {}: {} in {} games",
+        key,
+        career.to_string().bold(),
+        games
+    );
+
+    // Past milestones are reported rather than counted down to
+    if career >= target {
+        let past = career - target;
+        let status = if past == 0 {
+            format!("{} has reached {} {}.", name, target, key)
+        } else {
+            format!(
+                "{} has reached {} {} and is {} past it.",
+                name, target, key, past
+            )
+        };
+        println!("\n{}\n", status.green());
+        return Ok(());
+    }
+
+    let remaining = target - career;
+    println!(
+        "\n{}",
+        format!(
+            "{} needs {} more {} to reach {}.",
+            name, remaining, key, target
+        )
+        .green()
+    );
+
+    if project {
+        let season = &landing["featuredStats"]["regularSeason"]["subSeason"];
+        let season_games = season["gamesPlayed"].as_i64().unwrap_or(0);
+        let season_total = season[&key].as_i64().unwrap_or(0);
+        let team = landing["currentTeamAbbrev"].as_str().unwrap_or("");
+        let active = landing["isActive"].as_bool().unwrap_or(false);
+
+        if !active || team.is_empty() {
+            println!("{} isn't active, so there's no pace to project from.", name);
+        } else if season_games == 0 || season_total == 0 {
+            println!(
+                "{} has no {} in {} games this season, so there's no pace to project from.",
+                name, key, season_games
+            );
+        } else {
+            let pace = season_total as f64 / season_games as f64;
+            println!(
+                "This season: {} {} in {} games ({:.2} per game)",
+                season_total, key, season_games, pace
+            );
+            match project_date(client, team, pace, remaining).await? {
+                Ok(date) => println!(
+                    "At this pace {} reaches {} around {}.",
+                    name,
+                    target,
+                    date.format("%A, %B %-d, %Y").to_string().bold()
+                ),
+                Err((needed, left)) => println!(
+                    "At this pace {} needs about {} more games, but {} has {} left this season.",
+                    name, needed, team, left
+                ),
+            }
+        }
+    }
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,533 @@
+use chrono::Local;
+use colored::Colorize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use crate::api::{NHL_API_URL, nhl_api_request};
+use crate::standings::{
+    DIVISION_PLAYOFF_SPOTS, WILD_CARD_SPOTS, fetch_standings, team_abbrev, wildcard_standings,
+};
+use crate::tiebreakers::Tiebreaks;
+
+// Share of games that go past regulation that are decided in a shootout
+const SHOOTOUT_SHARE: f64 = 0.35;
+
+pub struct OddsOptions {
+    /// Number of seasons to simulate
+    pub simulations: usize,
+    /// Seed for the random number generator, a random seed is used when not given
+    pub seed: Option<u64>,
+    /// Per-game win model: "pct" (log5 on points %) or "even" (coin flip)
+    pub model: String,
+    /// Added to the home team's chance of winning
+    pub home_edge: f64,
+    /// Chance that a game goes past regulation
+    pub ot_rate: f64,
+}
+
+impl Default for OddsOptions {
+    fn default() -> OddsOptions {
+        OddsOptions {
+            simulations: 10000,
+            seed: None,
+            model: "pct".to_string(),
+            home_edge: 0.04,
+            ot_rate: 0.23,
+        }
+    }
+}
+
+/// Small deterministic random number generator (SplitMix64) so a seed always replays the
+/// same simulations
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// A regular season game that hasn't been played yet
+pub struct RemainingGame {
+    pub home: String,
+    pub away: String,
+}
+
+/// Every regular season game still to be played, walking the league schedule a week at a time
+pub async fn fetch_remaining_schedule(
+    client: &reqwest::Client,
+) -> Result<Vec<RemainingGame>, Box<dyn std::error::Error>> {
+    let mut games = Vec::new();
+    let mut date = Local::now().format("%Y-%m-%d").to_string();
+
+    loop {
+        let url = format!("{}/schedule/{}", NHL_API_URL, date);
+        let schedule = nhl_api_request(client, &url).await?;
+
+        for day in schedule["gameWeek"].as_array().into_iter().flatten() {
+            for game in day["games"].as_array().into_iter().flatten() {
+                let finished = matches!(game["gameState"].as_str(), Some("FINAL") | Some("OFF"));
+                if game["gameType"].as_i64() != Some(2) || finished {
+                    continue;
+                }
+                games.push(RemainingGame {
+                    home: game["homeTeam"]["abbrev"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    away: game["awayTeam"]["abbrev"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                });
+            }
+        }
+
+        let season_end = schedule["regularSeasonEndDate"].as_str().unwrap_or("");
+        match schedule["nextStartDate"].as_str() {
+            Some(next) if next <= season_end && next > date.as_str() => date = next.to_string(),
+            _ => break,
+        }
+    }
+
+    Ok(games)
+}
+
+// Chance of the home team winning a game
+fn home_win_probability(home: &Value, away: &Value, options: &OddsOptions) -> f64 {
+    let base = match options.model.as_str() {
+        "even" => 0.5,
+        _ => {
+            // log5: how often a team of strength a beats a team of strength b
+            let strength = |team: &Value| {
+                if team["gamesPlayed"].as_i64().unwrap_or(0) == 0 {
+                    0.5
+                } else {
+                    team["pointPctg"].as_f64().unwrap_or(0.5).clamp(0.01, 0.99)
+                }
+            };
+            let a = strength(home);
+            let b = strength(away);
+            a * (1.0 - b) / (a * (1.0 - b) + b * (1.0 - a))
+        }
+    };
+    (base + options.home_edge).clamp(0.01, 0.99)
+}
+
+/// Points each team is expected to finish with, adding its expected points from every
+/// remaining game to the points it already has
+pub fn expected_points(
+    teams: &[Value],
+    schedule: &[RemainingGame],
+    options: &OddsOptions,
+) -> HashMap<String, f64> {
+    let mut points: HashMap<String, f64> = teams
+        .iter()
+        .map(|t| {
+            (
+                team_abbrev(t).to_string(),
+                t["points"].as_i64().unwrap_or(0) as f64,
+            )
+        })
+        .collect();
+    let by_abbrev: HashMap<&str, &Value> = teams.iter().map(|t| (team_abbrev(t), t)).collect();
+
+    for game in schedule {
+        let (Some(home), Some(away)) = (
+            by_abbrev.get(game.home.as_str()),
+            by_abbrev.get(game.away.as_str()),
+        ) else {
+            continue;
+        };
+        // Winners get 2 points, losers get 1 when the game goes past regulation
+        let probability = home_win_probability(home, away, options);
+        *points.entry(game.home.clone()).or_default() +=
+            2.0 * probability + options.ot_rate * (1.0 - probability);
+        *points.entry(game.away.clone()).or_default() +=
+            2.0 * (1.0 - probability) + options.ot_rate * probability;
+    }
+
+    points
+}
+
+// A team's record through one simulated season, kept as plain numbers while the games are
+// played and only written back to the standings row once the season is over
+#[derive(Clone)]
+struct Record {
+    games: i64,
+    points: i64,
+    wins: i64,
+    losses: i64,
+    ot_losses: i64,
+    regulation_wins: i64,
+    regulation_plus_ot_wins: i64,
+}
+
+impl Record {
+    fn from_team(team: &Value) -> Record {
+        let stat = |key: &str| team[key].as_i64().unwrap_or(0);
+        Record {
+            games: stat("gamesPlayed"),
+            points: stat("points"),
+            wins: stat("wins"),
+            losses: stat("losses"),
+            ot_losses: stat("otLosses"),
+            regulation_wins: stat("regulationWins"),
+            regulation_plus_ot_wins: stat("regulationPlusOtWins"),
+        }
+    }
+
+    // Update the fields the standings grouping and tiebreakers read
+    fn write(&self, team: &mut Value) {
+        team["gamesPlayed"] = self.games.into();
+        team["points"] = self.points.into();
+        team["wins"] = self.wins.into();
+        team["losses"] = self.losses.into();
+        team["otLosses"] = self.ot_losses.into();
+        team["regulationWins"] = self.regulation_wins.into();
+        team["regulationPlusOtWins"] = self.regulation_plus_ot_wins.into();
+    }
+}
+
+// Playoff, division title and Presidents' Trophy counts for each team across all simulations
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    playoffs: usize,
+    division: usize,
+    presidents: usize,
+    points: i64,
+}
+
+fn simulate(
+    teams: &[Value],
+    schedule: &[RemainingGame],
+    options: &OddsOptions,
+    rng: &mut Rng,
+) -> HashMap<String, Tally> {
+    let mut tallies: HashMap<String, Tally> = HashMap::new();
+    // Only keep what the standings grouping and tiebreakers need
+    let teams: Vec<Value> = teams
+        .iter()
+        .map(|t| {
+            json!({
+                "teamAbbrev": t["teamAbbrev"],
+                "teamName": t["teamName"],
+                "divisionName": t["divisionName"],
+                "conferenceName": t["conferenceName"],
+                "gamesPlayed": t["gamesPlayed"],
+                "pointPctg": t["pointPctg"],
+                "points": t["points"],
+                "wins": t["wins"],
+                "losses": t["losses"],
+                "otLosses": t["otLosses"],
+                "regulationWins": t["regulationWins"],
+                "regulationPlusOtWins": t["regulationPlusOtWins"],
+                "goalDifferential": t["goalDifferential"],
+                "goalFor": t["goalFor"],
+            })
+        })
+        .collect();
+    let index: HashMap<&str, usize> = teams
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (team_abbrev(t), i))
+        .collect();
+    let probabilities: Vec<Option<(usize, usize, f64)>> = schedule
+        .iter()
+        .map(|game| {
+            let home = *index.get(game.home.as_str())?;
+            let away = *index.get(game.away.as_str())?;
+            Some((
+                home,
+                away,
+                home_win_probability(&teams[home], &teams[away], options),
+            ))
+        })
+        .collect();
+    let tiebreaks = Tiebreaks::default();
+    let start: Vec<Record> = teams.iter().map(Record::from_team).collect();
+    // The standings rows are reused every simulation, only their records are rewritten
+    let mut season = teams.clone();
+
+    for _ in 0..options.simulations {
+        let mut records = start.clone();
+
+        for (home, away, probability) in probabilities.iter().flatten() {
+            let home_wins = rng.next_f64() < *probability;
+            let extra_time = rng.next_f64() < options.ot_rate;
+            let shootout = extra_time && rng.next_f64() < SHOOTOUT_SHARE;
+            let (winner, loser) = if home_wins {
+                (*home, *away)
+            } else {
+                (*away, *home)
+            };
+
+            let winner = &mut records[winner];
+            winner.points += 2;
+            winner.wins += 1;
+            winner.games += 1;
+            if !shootout {
+                winner.regulation_plus_ot_wins += 1;
+            }
+            if !extra_time {
+                winner.regulation_wins += 1;
+            }
+            let loser = &mut records[loser];
+            loser.games += 1;
+            if extra_time {
+                loser.points += 1;
+                loser.ot_losses += 1;
+            } else {
+                loser.losses += 1;
+            }
+        }
+
+        for (team, record) in season.iter_mut().zip(&records) {
+            record.write(team);
+        }
+
+        for conference in wildcard_standings(&season, &tiebreaks) {
+            for (_, division_teams) in &conference.divisions {
+                for (i, team) in division_teams
+                    .iter()
+                    .take(DIVISION_PLAYOFF_SPOTS)
+                    .enumerate()
+                {
+                    let tally = tallies.entry(team_abbrev(team).to_string()).or_default();
+                    tally.playoffs += 1;
+                    if i == 0 {
+                        tally.division += 1;
+                    }
+                }
+            }
+            for team in conference.wild_card.iter().take(WILD_CARD_SPOTS) {
+                tallies
+                    .entry(team_abbrev(team).to_string())
+                    .or_default()
+                    .playoffs += 1;
+            }
+        }
+
+        let mut league: Vec<&Value> = season.iter().collect();
+        tiebreaks.sort(&mut league);
+        if let Some(first) = league.first() {
+            tallies
+                .entry(team_abbrev(first).to_string())
+                .or_default()
+                .presidents += 1;
+        }
+
+        for (team, record) in season.iter().zip(&records) {
+            tallies
+                .entry(team_abbrev(team).to_string())
+                .or_default()
+                .points += record.points;
+        }
+    }
+
+    tallies
+}
+
+fn percent(count: usize, total: usize) -> String {
+    let pct = 100.0 * count as f64 / total.max(1) as f64;
+    if count == total {
+        "100%".to_string()
+    } else if count == 0 {
+        "0%".to_string()
+    } else if pct < 0.1 {
+        "<0.1%".to_string()
+    } else if pct > 99.9 {
+        ">99.9%".to_string()
+    } else {
+        format!("{:.1}%", pct)
+    }
+}
+
+pub async fn display_odds(
+    client: &reqwest::Client,
+    options: &OddsOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !matches!(options.model.as_str(), "pct" | "even") {
+        println!("Invalid model. Use 'pct' or 'even'");
+        return Ok(());
+    }
+
+    let standings = fetch_standings(client, None, None).await?;
+    let teams: Vec<Value> = standings["standings"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let schedule = fetch_remaining_schedule(client).await?;
+
+    let seed = options
+        .seed
+        .unwrap_or_else(|| Local::now().timestamp_nanos_opt().unwrap_or(0) as u64);
+    let mut rng = Rng::new(seed);
+    let tallies = simulate(&teams, &schedule, options, &mut rng);
+
+    let header = format!(
+        "{:<24} {:>3} {:>4} {:>8} {:>8} {:>8}",
+        "Team", "PTS", "PROJ", "PLAYOFF", "DIV", "PRES"
+    );
+    let width = header.len();
+    let separator = "-".repeat(width);
+
+    println!("\n{}", separator);
+    println!("{:^width$}", "PLAYOFF ODDS".bold());
+    println!(
+        "{:^width$}",
+        format!(
+            "{} simulations, {} games left, seed {}",
+            options.simulations,
+            schedule.len(),
+            seed
+        )
+    );
+
+    for conference in wildcard_standings(&teams, &Tiebreaks::default()) {
+        println!("{}", separator);
+        println!(
+            "{:^width$}",
+            format!("{} CONFERENCE", conference.name.to_uppercase())
+        );
+        println!("{}", separator);
+        println!("{}", header.bold().underline());
+
+        let mut conference_teams: Vec<&Value> = conference
+            .divisions
+            .iter()
+            .flat_map(|(_, teams)| teams.iter().copied())
+            .collect();
+        let empty = Tally::default();
+        let tally = |team: &Value| tallies.get(team_abbrev(team)).unwrap_or(&empty);
+        conference_teams.sort_by_key(|team| {
+            (
+                std::cmp::Reverse(tally(team).playoffs),
+                std::cmp::Reverse(tally(team).points),
+            )
+        });
+
+        for team in conference_teams {
+            let tally = tally(team);
+            let projected = tally.points as f64 / options.simulations.max(1) as f64;
+            println!(
+                "{:<24} {:>3} {:>4.0} {:>8} {:>8} {:>8}",
+                team["teamName"]["default"].as_str().unwrap_or("Unknown"),
+                team["points"].as_i64().unwrap_or(0),
+                projected,
+                percent(tally.playoffs, options.simulations).bold(),
+                percent(tally.division, options.simulations),
+                percent(tally.presidents, options.simulations)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(abbrev: &str, division: &str, points: i64, point_pct: f64) -> Value {
+        json!({
+            "teamAbbrev": { "default": abbrev },
+            "teamName": { "default": abbrev },
+            "divisionName": division,
+            "conferenceName": "Eastern",
+            "gamesPlayed": 60,
+            "pointPctg": point_pct,
+            "points": points,
+            "wins": points / 2,
+            "regulationWins": points / 3,
+            "regulationPlusOtWins": points / 2,
+        })
+    }
+
+    fn standings() -> Vec<Value> {
+        ["A", "B", "C", "D", "E", "F", "G", "H"]
+            .iter()
+            .enumerate()
+            .map(|(i, abbrev)| {
+                let division = if i % 2 == 0 {
+                    "Atlantic"
+                } else {
+                    "Metropolitan"
+                };
+                let points = 80 - 3 * i as i64;
+                team(abbrev, division, points, points as f64 / 120.0)
+            })
+            .collect()
+    }
+
+    fn game(home: &str, away: &str) -> RemainingGame {
+        RemainingGame {
+            home: home.to_string(),
+            away: away.to_string(),
+        }
+    }
+
+    fn schedule() -> Vec<RemainingGame> {
+        vec![
+            game("A", "H"),
+            game("B", "G"),
+            game("C", "F"),
+            game("D", "E"),
+            game("H", "A"),
+            game("G", "B"),
+            game("F", "C"),
+            game("E", "D"),
+        ]
+    }
+
+    #[test]
+    fn simulate_is_deterministic_for_a_seed() {
+        let teams = standings();
+        let options = OddsOptions {
+            simulations: 200,
+            ..OddsOptions::default()
+        };
+
+        let first = simulate(&teams, &schedule(), &options, &mut Rng::new(42));
+        let second = simulate(&teams, &schedule(), &options, &mut Rng::new(42));
+        assert_eq!(first, second);
+        assert_eq!(first.values().map(|t| t.presidents).sum::<usize>(), 200);
+    }
+
+    #[test]
+    fn expected_points_adds_win_and_overtime_points() {
+        let teams = standings();
+        let options = OddsOptions::default();
+        let points = expected_points(&teams, &[game("B", "G")], &options);
+
+        let p = home_win_probability(&teams[1], &teams[6], &options);
+        let ot = options.ot_rate;
+        assert!((points["B"] - (77.0 + 2.0 * p + ot * (1.0 - p))).abs() < 1e-9);
+        assert!((points["G"] - (62.0 + 2.0 * (1.0 - p) + ot * p)).abs() < 1e-9);
+        assert_eq!(points["A"], 80.0);
+
+        // A coin flip gives each side a win half the time and an overtime loss otherwise
+        let even = OddsOptions {
+            model: "even".to_string(),
+            home_edge: 0.0,
+            ot_rate: 0.2,
+            ..OddsOptions::default()
+        };
+        let points = expected_points(&teams, &[game("B", "G")], &even);
+        assert!((points["B"] - 78.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,370 @@
+use colored::Colorize;
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::api::{NHL_API_URL, nhl_api_request};
+
+// Events that only mark the flow of the game, shown as period headers instead of rows
+const FLOW_EVENTS: [&str; 5] = [
+    "period-start",
+    "period-end",
+    "game-end",
+    "shootout-complete",
+    "game-official",
+];
+
+pub async fn fetch_play_by_play(
+    client: &reqwest::Client,
+    game_id: &str,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let url = format!("{}/gamecenter/{}/play-by-play", NHL_API_URL, game_id);
+    nhl_api_request(client, &url).await
+}
+
+/// Map of player id to "First Last" built from the roster spots of a play-by-play payload
+pub fn player_names(pbp: &Value) -> HashMap<i64, String> {
+    let mut names = HashMap::new();
+    if let Some(roster) = pbp["rosterSpots"].as_array() {
+        for player in roster {
+            let id = player["playerId"].as_i64().unwrap_or(0);
+            let name = format!(
+                "{} {}",
+                player["firstName"]["default"].as_str().unwrap_or(""),
+                player["lastName"]["default"].as_str().unwrap_or("")
+            );
+            names.insert(id, name);
+        }
+    }
+    names
+}
+
+pub fn team_abbrev(pbp: &Value, team_id: i64) -> &str {
+    if pbp["awayTeam"]["id"].as_i64() == Some(team_id) {
+        pbp["awayTeam"]["abbrev"].as_str().unwrap_or("AWY")
+    } else if pbp["homeTeam"]["id"].as_i64() == Some(team_id) {
+        pbp["homeTeam"]["abbrev"].as_str().unwrap_or("HOM")
+    } else {
+        ""
+    }
+}
+
+/// Label of a period of the game. Period 5 is the shootout in the regular season, but in
+/// the playoffs overtime goes on (2OT, 3OT, ...) until someone scores.
+pub fn period_label(pbp: &Value, period: i64) -> String {
+    let playoffs = pbp["gameType"].as_i64() == Some(3);
+    match period {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        4 => "OT".to_string(),
+        5 if !playoffs => "SO".to_string(),
+        n => format!("{}OT", n - 3),
+    }
+}
+
+/// Convert a "MM:SS" clock string into seconds
+pub fn clock_seconds(clock: &str) -> i64 {
+    let mut parts = clock.split(':');
+    let minutes = parts
+        .next()
+        .and_then(|m| m.parse::<i64>().ok())
+        .unwrap_or(0);
+    let seconds = parts
+        .next()
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(0);
+    minutes * 60 + seconds
+}
+
+/// Strength state of a play from the point of view of the team that owns it, e.g. "5v4".
+/// The situation code is four digits: away goalie, away skaters, home skaters, home goalie.
+pub fn strength_state(pbp: &Value, play: &Value) -> String {
+    let code: Vec<u32> = play["situationCode"]
+        .as_str()
+        .unwrap_or("")
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .collect();
+    if code.len() != 4 {
+        return String::new();
+    }
+
+    let owner = play["details"]["eventOwnerTeamId"].as_i64();
+    let home_owned = owner.is_some() && owner == pbp["homeTeam"]["id"].as_i64();
+    let (own_skaters, opp_skaters, opp_goalie) = if home_owned {
+        (code[2], code[1], code[0])
+    } else {
+        (code[1], code[2], code[3])
+    };
+
+    let mut state = format!("{}v{}", own_skaters, opp_skaters);
+    if opp_goalie == 0 && owner.is_some() {
+        state.push_str(" EN");
+    }
+    state
+}
+
+/// Team id credited with a shot attempt. Blocked shots are owned by the blocking team,
+/// so the attempt belongs to their opponent.
+pub fn shooting_team_id(pbp: &Value, play: &Value) -> Option<i64> {
+    let owner = play["details"]["eventOwnerTeamId"].as_i64()?;
+    if play["typeDescKey"].as_str() == Some("blocked-shot") {
+        let away_id = pbp["awayTeam"]["id"].as_i64()?;
+        let home_id = pbp["homeTeam"]["id"].as_i64()?;
+        return Some(if owner == away_id { home_id } else { away_id });
+    }
+    Some(owner)
+}
+
+pub fn is_shot_attempt(play: &Value) -> bool {
+    matches!(
+        play["typeDescKey"].as_str().unwrap_or(""),
+        "shot-on-goal" | "missed-shot" | "blocked-shot" | "goal"
+    )
+}
+
+// Map the short names accepted on the command line to the api's event type keys
+fn event_type_key(name: &str) -> String {
+    match name {
+        "shot" | "shots" | "sog" => "shot-on-goal".to_string(),
+        "miss" | "missed" => "missed-shot".to_string(),
+        "block" | "blocked" => "blocked-shot".to_string(),
+        "hits" => "hit".to_string(),
+        "goals" => "goal".to_string(),
+        "penalties" => "penalty".to_string(),
+        "faceoffs" => "faceoff".to_string(),
+        "giveaways" => "giveaway".to_string(),
+        "takeaways" => "takeaway".to_string(),
+        "stoppages" => "stoppage".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn event_label(type_key: &str) -> &str {
+    match type_key {
+        "faceoff" => "Faceoff",
+        "shot-on-goal" => "Shot",
+        "missed-shot" => "Missed Shot",
+        "blocked-shot" => "Blocked Shot",
+        "hit" => "Hit",
+        "giveaway" => "Giveaway",
+        "takeaway" => "Takeaway",
+        "penalty" => "Penalty",
+        "goal" => "Goal",
+        "stoppage" => "Stoppage",
+        "delayed-penalty" => "Delayed Penalty",
+        "failed-shot-attempt" => "Failed Shot",
+        other => other,
+    }
+}
+
+// The player ids involved in a play, used for player filtering
+fn players_in_play(play: &Value) -> Vec<i64> {
+    [
+        "winningPlayerId",
+        "losingPlayerId",
+        "shootingPlayerId",
+        "goalieInNetId",
+        "blockingPlayerId",
+        "hittingPlayerId",
+        "hitteePlayerId",
+        "playerId",
+        "committedByPlayerId",
+        "drawnByPlayerId",
+        "servedByPlayerId",
+        "scoringPlayerId",
+        "assist1PlayerId",
+        "assist2PlayerId",
+    ]
+    .iter()
+    .filter_map(|key| play["details"][*key].as_i64())
+    .collect()
+}
+
+fn describe_play(play: &Value, names: &HashMap<i64, String>) -> String {
+    let details = &play["details"];
+    let name = |key: &str| {
+        details[key]
+            .as_i64()
+            .and_then(|id| names.get(&id))
+            .map(|n| n.as_str())
+            .unwrap_or("Unknown")
+    };
+    let shot_type = details["shotType"].as_str().unwrap_or("shot");
+    let reason = details["reason"].as_str().unwrap_or("").replace('-', " ");
+
+    match play["typeDescKey"].as_str().unwrap_or("") {
+        "faceoff" => format!(
+            "{} won vs {} ({}Z)",
+            name("winningPlayerId"),
+            name("losingPlayerId"),
+            details["zoneCode"].as_str().unwrap_or("")
+        ),
+        "shot-on-goal" => {
+            if details["goalieInNetId"].is_null() {
+                format!("{} {}, empty net", name("shootingPlayerId"), shot_type)
+            } else {
+                format!(
+                    "{} {}, saved by {}",
+                    name("shootingPlayerId"),
+                    shot_type,
+                    name("goalieInNetId")
+                )
+            }
+        }
+        "missed-shot" => format!("{} {}, {}", name("shootingPlayerId"), shot_type, reason),
+        "blocked-shot" => format!(
+            "{} blocked by {}",
+            name("shootingPlayerId"),
+            name("blockingPlayerId")
+        ),
+        "hit" => format!("{} hit {}", name("hittingPlayerId"), name("hitteePlayerId")),
+        "giveaway" | "takeaway" => name("playerId").to_string(),
+        "penalty" => {
+            let mut text = format!(
+                "{} {} ({} min)",
+                if details["committedByPlayerId"].is_null() {
+                    "Team"
+                } else {
+                    name("committedByPlayerId")
+                },
+                details["descKey"].as_str().unwrap_or("").replace('-', " "),
+                details["duration"].as_i64().unwrap_or(0)
+            );
+            if !details["drawnByPlayerId"].is_null() {
+                text.push_str(&format!(", drawn by {}", name("drawnByPlayerId")));
+            }
+            if !details["servedByPlayerId"].is_null() {
+                text.push_str(&format!(", served by {}", name("servedByPlayerId")));
+            }
+            text
+        }
+        "goal" => {
+            let mut assists = Vec::new();
+            for key in ["assist1PlayerId", "assist2PlayerId"] {
+                if !details[key].is_null() {
+                    assists.push(name(key));
+                }
+            }
+            let assist_text = if assists.is_empty() {
+                "Unassisted".to_string()
+            } else {
+                format!("Assists: {}", assists.join(", "))
+            };
+            format!(
+                "{} {} ({}) {}-{}",
+                name("scoringPlayerId"),
+                shot_type,
+                assist_text,
+                details["awayScore"].as_i64().unwrap_or(0),
+                details["homeScore"].as_i64().unwrap_or(0)
+            )
+        }
+        "stoppage" => reason,
+        _ => String::new(),
+    }
+}
+
+pub async fn display_pbp(
+    client: &reqwest::Client,
+    game_id: &str,
+    event_types: Option<&str>,
+    team: Option<&str>,
+    player: Option<&str>,
+    period: Option<i64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pbp = fetch_play_by_play(client, game_id).await?;
+    let names = player_names(&pbp);
+    let separator = "=".repeat(80);
+
+    let away_team = pbp["awayTeam"]["abbrev"].as_str().unwrap_or("AWY");
+    let home_team = pbp["homeTeam"]["abbrev"].as_str().unwrap_or("HOM");
+
+    println!("\n{}", separator);
+    println!(
+        "{:^80}",
+        format!("{} @ {} - PLAY BY PLAY", away_team, home_team).bold()
+    );
+    println!("{}", separator);
+
+    let event_types: Vec<String> = event_types
+        .map(|types| {
+            types
+                .split(',')
+                .map(|t| event_type_key(t.trim().to_lowercase().as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let player = player.map(|p| p.to_lowercase());
+
+    let mut current_period = 0;
+    let mut shown = 0;
+
+    if let Some(plays) = pbp["plays"].as_array() {
+        for play in plays {
+            let type_key = play["typeDescKey"].as_str().unwrap_or("");
+            let play_period = play["periodDescriptor"]["number"].as_i64().unwrap_or(0);
+
+            if FLOW_EVENTS.contains(&type_key) {
+                continue;
+            }
+            if !event_types.is_empty() && !event_types.iter().any(|t| t == type_key) {
+                continue;
+            }
+            if period.is_some_and(|p| p != play_period) {
+                continue;
+            }
+            let owner = play["details"]["eventOwnerTeamId"].as_i64().unwrap_or(0);
+            let owner_abbrev = team_abbrev(&pbp, owner);
+            if let Some(team) = team {
+                if !owner_abbrev.eq_ignore_ascii_case(team) {
+                    continue;
+                }
+            }
+            if let Some(player) = &player {
+                let involved = players_in_play(play).iter().any(|id| {
+                    names
+                        .get(id)
+                        .is_some_and(|n| n.to_lowercase().contains(player.as_str()))
+                });
+                if !involved {
+                    continue;
+                }
+            }
+
+            if play_period != current_period {
+                current_period = play_period;
+                println!("\n{}", period_label(&pbp, play_period).bold());
+                println!(
+                    "{}",
+                    format!(
+                        "{:<6} {:<7} {:<4} {:<15} {}",
+                        "Time", "Str", "Team", "Event", "Details"
+                    )
+                    .bold()
+                    .underline()
+                );
+            }
+
+            let row = format!(
+                "{:<6} {:<7} {:<4} {:<15} {}",
+                play["timeInPeriod"].as_str().unwrap_or(""),
+                strength_state(&pbp, play),
+                owner_abbrev,
+                event_label(type_key),
+                describe_play(play, &names)
+            );
+            if type_key == "goal" {
+                println!("{}", row.green().bold());
+            } else {
+                println!("{}", row);
+            }
+            shown += 1;
+        }
+    }
+
+    if shown == 0 {
+        println!("No plays found");
+    }
+
+    Ok(())
+}
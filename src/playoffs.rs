@@ -0,0 +1,445 @@
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde_json::Value;
+use crate::api::{NHL_API_URL, nhl_api_request, season_id};
+use crate::standings::{fetch_standings, team_abbrev, wildcard_standings};
+use crate::tiebreakers::Tiebreaks;
+
+// Every round is a best of seven
+pub const WINS_NEEDED: i64 = 4;
+const ROUNDS: usize = 4;
+pub const ROUND_NAMES: [&str; ROUNDS] = ["1st Round", "2nd Round", "Conf Final", "Cup Final"];
+
+// A team's line in the bracket is its seed, abbreviation and series wins
+const CELL_WIDTH: usize = 10;
+const GUTTER_WIDTH: usize = 3;
+// Each first round series takes 4 rows of the bracket, so 8 of them fill it
+const BRACKET_ROWS: usize = 32;
+
+/// One side of a playoff series
+pub struct Seed {
+    /// Playoff seed, e.g. "D1" or "WC2"
+    pub label: String,
+    /// Team abbreviation, unknown until the earlier rounds are decided
+    pub abbrev: Option<String>,
+    pub wins: i64,
+}
+
+impl Seed {
+    fn to_be_decided() -> Seed {
+        Seed {
+            label: String::new(),
+            abbrev: None,
+            wins: 0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.abbrev.as_deref().unwrap_or("TBD")
+    }
+}
+
+pub struct Series {
+    /// Series letter, A to H in the first round through O for the final
+    pub letter: String,
+    pub round: usize,
+    pub top: Seed,
+    pub bottom: Seed,
+}
+
+impl Series {
+    fn from_bracket(series: &Value) -> Series {
+        let seed = |side: &str| Seed {
+            label: series[format!("{}SeedRankAbbrev", side)]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            abbrev: series[format!("{}SeedTeam", side)]["abbrev"]
+                .as_str()
+                .map(|a| a.to_string()),
+            wins: series[format!("{}SeedWins", side)].as_i64().unwrap_or(0),
+        };
+        Series {
+            letter: series["seriesLetter"].as_str().unwrap_or("").to_string(),
+            round: series["playoffRound"].as_u64().unwrap_or(1) as usize,
+            top: seed("top"),
+            bottom: seed("bottom"),
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.top.abbrev.is_some() && self.bottom.abbrev.is_some()
+    }
+
+    fn winner(&self) -> Option<&Seed> {
+        [&self.top, &self.bottom]
+            .into_iter()
+            .find(|seed| seed.wins == WINS_NEEDED)
+    }
+
+    pub fn status(&self) -> String {
+        if !self.is_set() {
+            return "Matchup to be decided".to_string();
+        }
+        let (leader, trailer) = if self.top.wins >= self.bottom.wins {
+            (&self.top, &self.bottom)
+        } else {
+            (&self.bottom, &self.top)
+        };
+        if leader.wins == WINS_NEEDED {
+            format!("{} wins {}-{}", leader.name(), leader.wins, trailer.wins)
+        } else if leader.wins == 0 {
+            "Series not started".to_string()
+        } else if leader.wins == trailer.wins {
+            format!("Series tied {}-{}", leader.wins, trailer.wins)
+        } else {
+            format!("{} leads {}-{}", leader.name(), leader.wins, trailer.wins)
+        }
+    }
+}
+
+// First row of a series in the bracket. Rounds double the rows each series spans, so a
+// series sits halfway between the two it is fed by.
+fn series_row(round: usize, index: usize) -> usize {
+    let block = 4 << round;
+    index * block + block / 2 - 1
+}
+
+// A team's line in the bracket, green when it won the series and dimmed when it lost
+fn bracket_cell(series: &Series, seed: &Seed) -> String {
+    let wins = if series.is_set() {
+        seed.wins.to_string()
+    } else {
+        String::new()
+    };
+    let text = format!(
+        "{:<width$}",
+        format!("{:<3} {:<3} {:>2}", seed.label, seed.name(), wins),
+        width = CELL_WIDTH
+    );
+    if seed.wins == WINS_NEEDED {
+        text.green().bold().to_string()
+    } else if series.winner().is_some() {
+        text.dimmed().to_string()
+    } else {
+        text
+    }
+}
+
+fn print_bracket(series: &[Series]) {
+    let columns = ROUNDS * 2 - 1;
+    let mut grid: Vec<Vec<String>> = (0..BRACKET_ROWS)
+        .map(|_| {
+            (0..columns)
+                .map(|column| {
+                    let width = if column % 2 == 0 {
+                        CELL_WIDTH
+                    } else {
+                        GUTTER_WIDTH
+                    };
+                    " ".repeat(width)
+                })
+                .collect()
+        })
+        .collect();
+
+    for round in 0..ROUNDS {
+        let mut round_series: Vec<&Series> =
+            series.iter().filter(|s| s.round == round + 1).collect();
+        round_series.sort_by(|a, b| a.letter.cmp(&b.letter));
+
+        for (index, s) in round_series.iter().enumerate() {
+            let row = series_row(round, index);
+            if row + 1 >= BRACKET_ROWS {
+                continue;
+            }
+            grid[row][round * 2] = bracket_cell(s, &s.top);
+            grid[row + 1][round * 2] = bracket_cell(s, &s.bottom);
+        }
+
+        // Join each pair of series to the one their winners meet in
+        if round + 1 < ROUNDS {
+            for next in 0..(8 >> (round + 1)) {
+                let top = series_row(round, next * 2);
+                let bottom = series_row(round, next * 2 + 1);
+                let joined = series_row(round + 1, next);
+                for (row, line) in grid.iter_mut().enumerate().take(bottom + 1).skip(top) {
+                    line[round * 2 + 1] = if row == top {
+                        "─┐ "
+                    } else if row == bottom {
+                        "─┘ "
+                    } else if row == joined {
+                        " ├─"
+                    } else {
+                        " │ "
+                    }
+                    .to_string();
+                }
+            }
+        }
+    }
+
+    let header: Vec<String> = ROUND_NAMES
+        .iter()
+        .map(|name| format!("{:<width$}", name, width = CELL_WIDTH + GUTTER_WIDTH))
+        .collect();
+    println!("\n{}", header.concat().bold());
+    for (row, line) in grid.iter().enumerate() {
+        // Eastern Conference series fill the top half of the bracket
+        let conference = match row {
+            0 => "EAST".bold().to_string(),
+            16 => "WEST".bold().to_string(),
+            _ => String::new(),
+        };
+        println!("{}  {}", line.concat(), conference);
+    }
+}
+
+/// "OT", "2OT", ... for a finished schedule game decided in overtime
+pub fn overtime_label(game: &Value) -> Option<String> {
+    if game["gameOutcome"]["lastPeriodType"].as_str() != Some("OT") {
+        return None;
+    }
+    match game["gameOutcome"]["otPeriods"].as_i64().unwrap_or(1) {
+        overtimes if overtimes > 1 => Some(format!("{}OT", overtimes)),
+        _ => Some("OT".to_string()),
+    }
+}
+
+// Game by game results of a series and when the next game is
+async fn print_series_games(
+    client: &reqwest::Client,
+    season_id: &str,
+    series: &Series,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let url = format!(
+        "{}/schedule/playoff-series/{}/{}",
+        NHL_API_URL,
+        season_id,
+        series.letter.to_lowercase()
+    );
+    let schedule = nhl_api_request(client, &url).await?;
+
+    for (i, game) in schedule["games"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let start = game["startTimeUTC"]
+            .as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Local));
+        let date = start
+            .map(|t| t.format("%a %b %-d").to_string())
+            .unwrap_or_default();
+        let away = game["awayTeam"]["abbrev"].as_str().unwrap_or("");
+        let home = game["homeTeam"]["abbrev"].as_str().unwrap_or("");
+        let away_score = game["awayTeam"]["score"].as_i64().unwrap_or(0);
+        let home_score = game["homeTeam"]["score"].as_i64().unwrap_or(0);
+
+        match game["gameState"].as_str().unwrap_or("") {
+            "FINAL" | "OFF" => {
+                let ending = match overtime_label(game) {
+                    Some(label) => format!(" ({})", label),
+                    None => String::new(),
+                };
+                println!(
+                    "    G{} {:<11} {} {} @ {} {}{}",
+                    i + 1,
+                    date,
+                    away,
+                    away_score,
+                    home,
+                    home_score,
+                    ending
+                );
+            }
+            "LIVE" | "CRIT" => {
+                println!(
+                    "    G{} {:<11} {} {} @ {} {}  {}",
+                    i + 1,
+                    date,
+                    away,
+                    away_score,
+                    home,
+                    home_score,
+                    "LIVE".red().bold()
+                );
+            }
+            _ => {
+                let time = start
+                    .map(|t| t.format("%-I:%M %p").to_string())
+                    .unwrap_or_default();
+                println!(
+                    "    {} Game {}, {} {}, {} @ {}",
+                    "Next:".bold(),
+                    i + 1,
+                    date,
+                    time,
+                    away,
+                    home
+                );
+                // Later games are only played if the series goes that long
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Every series in a season's playoff bracket, empty until the playoffs start
+pub async fn fetch_bracket(
+    client: &reqwest::Client,
+    season_id: &str,
+) -> Result<Vec<Series>, Box<dyn std::error::Error>> {
+    let url = format!("{}/playoff-bracket/{}", NHL_API_URL, &season_id[4..8]);
+    let bracket = nhl_api_request(client, &url).await?;
+    Ok(bracket["series"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(Series::from_bracket)
+        .collect())
+}
+
+// The first round if the season ended today. In each conference the division winner with the
+// better record plays the second wild card, the other winner plays the first wild card, and
+// the second and third place teams in each division play each other.
+fn projected_bracket(teams: &[Value], tiebreaks: &Tiebreaks) -> Vec<Series> {
+    let mut matchups = Vec::new();
+
+    for conference in wildcard_standings(teams, tiebreaks) {
+        let mut winners: Vec<&Value> = conference
+            .divisions
+            .iter()
+            .filter_map(|(_, division_teams)| division_teams.first().copied())
+            .collect();
+        tiebreaks.sort(&mut winners);
+        let best_winner = winners.first().map(|team| team_abbrev(team));
+
+        for (division_name, division_teams) in &conference.divisions {
+            let initial = division_name.chars().next().unwrap_or('?');
+            let seed = |place: usize| Seed {
+                label: format!("{}{}", initial, place + 1),
+                abbrev: division_teams
+                    .get(place)
+                    .map(|team| team_abbrev(team).to_string()),
+                wins: 0,
+            };
+            let leader = division_teams.first().map(|team| team_abbrev(team));
+            let wild_card = if leader.is_some() && leader == best_winner {
+                1
+            } else {
+                0
+            };
+
+            matchups.push((
+                seed(0),
+                Seed {
+                    label: format!("WC{}", wild_card + 1),
+                    abbrev: conference
+                        .wild_card
+                        .get(wild_card)
+                        .map(|team| team_abbrev(team).to_string()),
+                    wins: 0,
+                },
+            ));
+            matchups.push((seed(1), seed(2)));
+        }
+    }
+
+    let mut bracket: Vec<Series> = matchups
+        .into_iter()
+        .map(|(top, bottom)| Series {
+            letter: String::new(),
+            round: 1,
+            top,
+            bottom,
+        })
+        .collect();
+    // Later rounds depend on who wins
+    for round in 2..=ROUNDS {
+        for _ in 0..(8 >> (round - 1)) {
+            bracket.push(Series {
+                letter: String::new(),
+                round,
+                top: Seed::to_be_decided(),
+                bottom: Seed::to_be_decided(),
+            });
+        }
+    }
+    for (i, series) in bracket.iter_mut().enumerate() {
+        series.letter = ((b'A' + i as u8) as char).to_string();
+    }
+
+    bracket
+}
+
+pub async fn display_playoffs(
+    client: &reqwest::Client,
+    season: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let season_id = season_id(client, season).await?;
+    let season_label = format!("{}-{}", &season_id[0..4], &season_id[6..8]);
+    let series = fetch_bracket(client, &season_id).await?;
+    let started = series.iter().any(|s| s.round == 1 && s.is_set());
+
+    let separator = "=".repeat(70);
+    println!("\n{}", separator);
+
+    if !started {
+        if season.is_some() {
+            println!("{:^70}", format!("No playoff bracket for {}", season_label));
+            println!("{}", separator);
+            return Ok(());
+        }
+
+        println!("{:^70}", format!("{} PLAYOFF PICTURE", season_label).bold());
+        println!("{:^70}", "First round matchups if the season ended today");
+        println!("{}", separator);
+
+        let standings = fetch_standings(client, None, None).await?;
+        let teams: Vec<Value> = standings["standings"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let tiebreaks = Tiebreaks::load(client, &teams).await?;
+        print_bracket(&projected_bracket(&teams, &tiebreaks));
+        return Ok(());
+    }
+
+    println!(
+        "{:^70}",
+        format!("{} STANLEY CUP PLAYOFFS", season_label).bold()
+    );
+    println!("{}", separator);
+    print_bracket(&series);
+
+    for round in 1..=ROUNDS {
+        let mut round_series: Vec<&Series> = series
+            .iter()
+            .filter(|s| s.round == round && s.is_set())
+            .collect();
+        if round_series.is_empty() {
+            continue;
+        }
+        round_series.sort_by(|a, b| a.letter.cmp(&b.letter));
+
+        println!("\n{}", ROUND_NAMES[round - 1].to_uppercase().bold());
+        println!("{}", "-".repeat(70));
+        for s in round_series {
+            println!(
+                "{} {} vs {}  {}",
+                s.letter.bold(),
+                s.top.name(),
+                s.bottom.name(),
+                s.status()
+            );
+            print_series_games(client, &season_id, s).await?;
+        }
+    }
+
+    Ok(())
+}
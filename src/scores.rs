@@ -7,7 +7,7 @@ pub async fn display_scores(client: &reqwest::Client) -> Result<(), Box<dyn std:
         .format("%Y-%m-%d")
         .to_string();
     let url = format!("{}/schedule/{}", NHL_API_URL, yesterday);
-    let schedule = nhl_api_request(&client, &url).await?;
+    let schedule = nhl_api_request(client, &url).await?;
     let separator = "-".repeat(52);
 
     for idx in 0..schedule["gameWeek"].as_array().unwrap().len().min(3) {
@@ -24,7 +24,7 @@ pub async fn display_scores(client: &reqwest::Client) -> Result<(), Box<dyn std:
             println!("{:^52}", date);
             println!("{}", separator);
 
-            if games.len() == 0 {
+            if games.is_empty() {
                 println!("{:^52}", "No games scheduled for today");
                 continue;
             }
@@ -0,0 +1,263 @@
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::api::{NHL_API_URL, nhl_api_request, season_id};
+use crate::boxscores::period_scores;
+use crate::playoffs::{ROUND_NAMES, WINS_NEEDED, fetch_bracket, overtime_label};
+
+const SERIES_LEADERS: usize = 10;
+
+// A player's scoring across the series
+struct SeriesLeader {
+    name: String,
+    team: String,
+    goals: i64,
+    assists: i64,
+}
+
+fn full_name(player: &Value) -> String {
+    format!(
+        "{} {}",
+        player["firstName"]["default"].as_str().unwrap_or(""),
+        player["lastName"]["default"].as_str().unwrap_or("")
+    )
+}
+
+// Goalies credited with a win or loss for one side of a game, with their saves
+fn goalie_decisions(boxscore: &Value, side: &str) -> Vec<String> {
+    boxscore["playerByGameStats"][side]["goalies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|goalie| {
+            let decision = goalie["decision"].as_str()?;
+            Some(format!(
+                "{}: {} ({})",
+                decision,
+                goalie["name"]["default"].as_str().unwrap_or("Unknown"),
+                goalie["saveShotsAgainst"].as_str().unwrap_or("-")
+            ))
+        })
+        .collect()
+}
+
+fn add_series_points(leaders: &mut HashMap<i64, SeriesLeader>, landing: &Value) {
+    for period in landing["summary"]["scoring"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        if period["periodDescriptor"]["periodType"].as_str() == Some("SO") {
+            continue;
+        }
+        for goal in period["goals"].as_array().into_iter().flatten() {
+            let team = goal["teamAbbrev"]["default"].as_str().unwrap_or("");
+            let mut credit = |player: &Value, goals: i64, assists: i64| {
+                let leader = leaders
+                    .entry(player["playerId"].as_i64().unwrap_or(0))
+                    .or_insert_with(|| SeriesLeader {
+                        name: full_name(player),
+                        team: team.to_string(),
+                        goals: 0,
+                        assists: 0,
+                    });
+                leader.goals += goals;
+                leader.assists += assists;
+            };
+
+            credit(goal, 1, 0);
+            for assist in goal["assists"].as_array().into_iter().flatten() {
+                credit(assist, 0, 1);
+            }
+        }
+    }
+}
+
+// Goals by period for both teams, with an OT column when the game went past regulation
+fn print_period_scores(landing: &Value) {
+    let Some((away_scores, home_scores)) = period_scores(landing) else {
+        return;
+    };
+    let overtime = landing["periodDescriptor"]["number"].as_i64().unwrap_or(0) > 3;
+    let periods = if overtime { 4 } else { 3 };
+    let labels = ["1st", "2nd", "3rd", "OT"];
+
+    let mut header = format!("    {:<4}", "");
+    for label in labels.iter().take(periods) {
+        header.push_str(&format!(" {:>4}", label));
+    }
+    header.push_str(&format!(" {:>6}", "Final"));
+    println!("{}", header.dimmed());
+
+    for (side, scores) in [("awayTeam", away_scores), ("homeTeam", home_scores)] {
+        let mut row = format!("    {:<4}", landing[side]["abbrev"].as_str().unwrap_or(""));
+        for score in scores.iter().take(periods) {
+            row.push_str(&format!(" {:>4}", score));
+        }
+        let total: i32 = scores.iter().sum();
+        row.push_str(&format!(" {:>6}", total.to_string().bold()));
+        println!("{}", row);
+    }
+}
+
+pub async fn display_series(
+    client: &reqwest::Client,
+    team: &str,
+    season: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let team = team.to_uppercase();
+    let season_id = season_id(client, season).await?;
+    let bracket = fetch_bracket(client, &season_id).await?;
+
+    // A team's latest series is the one in the furthest round it reached
+    let plays_in = |abbrev: &Option<String>| abbrev.as_deref() == Some(team.as_str());
+    let Some(series) = bracket
+        .iter()
+        .filter(|s| s.is_set() && (plays_in(&s.top.abbrev) || plays_in(&s.bottom.abbrev)))
+        .max_by_key(|s| s.round)
+    else {
+        println!("No playoff series found for {}", team);
+        return Ok(());
+    };
+
+    let separator = "=".repeat(70);
+    println!("\n{}", separator);
+    println!(
+        "{:^70}",
+        format!("{} vs {}", series.top.name(), series.bottom.name()).bold()
+    );
+    println!(
+        "{:^70}",
+        format!(
+            "{}, series {}",
+            ROUND_NAMES.get(series.round - 1).unwrap_or(&"Playoffs"),
+            series.letter
+        )
+    );
+    println!("{:^70}", series.status().bold());
+    println!("{}", separator);
+
+    let url = format!(
+        "{}/schedule/playoff-series/{}/{}",
+        NHL_API_URL,
+        season_id,
+        series.letter.to_lowercase()
+    );
+    let schedule = nhl_api_request(client, &url).await?;
+
+    let mut leaders: HashMap<i64, SeriesLeader> = HashMap::new();
+    let mut upcoming = Vec::new();
+
+    for (i, game) in schedule["games"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let game_number = i + 1;
+        let start = game["startTimeUTC"]
+            .as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Local));
+        let state = game["gameState"].as_str().unwrap_or("");
+        if state == "FUT" || state == "PRE" {
+            upcoming.push((game_number, start, game));
+            continue;
+        }
+
+        let game_id = game["id"].as_i64().unwrap_or(0);
+        let url = format!("{}/gamecenter/{}/landing", NHL_API_URL, game_id);
+        let landing = nhl_api_request(client, &url).await?;
+        let url = format!("{}/gamecenter/{}/boxscore", NHL_API_URL, game_id);
+        let boxscore = nhl_api_request(client, &url).await?;
+
+        let result = match state {
+            "LIVE" | "CRIT" => "LIVE".red().bold().to_string(),
+            _ => match overtime_label(game) {
+                Some(label) => label.yellow().to_string(),
+                None => String::new(),
+            },
+        };
+        println!(
+            "\n{}  {}  {} @ {}  {}",
+            format!("GAME {}", game_number).bold(),
+            start
+                .map(|t| t.format("%a %b %-d").to_string())
+                .unwrap_or_default(),
+            game["awayTeam"]["abbrev"].as_str().unwrap_or(""),
+            game["homeTeam"]["abbrev"].as_str().unwrap_or(""),
+            result
+        );
+        print_period_scores(&landing);
+
+        let decisions: Vec<String> = ["awayTeam", "homeTeam"]
+            .iter()
+            .flat_map(|side| goalie_decisions(&boxscore, side))
+            .collect();
+        if !decisions.is_empty() {
+            println!("    {}", decisions.join("   ").dimmed());
+        }
+
+        add_series_points(&mut leaders, &landing);
+    }
+
+    if !leaders.is_empty() {
+        let mut leaders: Vec<SeriesLeader> = leaders.into_values().collect();
+        leaders.sort_by_key(|l| (-(l.goals + l.assists), -l.goals));
+
+        println!("\n{}", "SERIES LEADERS".bold());
+        println!("{}", "-".repeat(70));
+        println!(
+            "{}",
+            format!(
+                "{:<24} {:<4} {:>3} {:>3} {:>4}",
+                "Player", "Team", "G", "A", "PTS"
+            )
+            .bold()
+            .underline()
+        );
+        for leader in leaders.iter().take(SERIES_LEADERS) {
+            println!(
+                "{:<24} {:<4} {:>3} {:>3} {:>4}",
+                leader.name,
+                leader.team,
+                leader.goals,
+                leader.assists,
+                (leader.goals + leader.assists).to_string().bold()
+            );
+        }
+    }
+
+    let leader_wins = series.top.wins.max(series.bottom.wins);
+    if !upcoming.is_empty() && leader_wins < WINS_NEEDED {
+        // Games past the fewest the series can still take are only played if needed
+        let played = (series.top.wins + series.bottom.wins) as usize;
+        let needed = played + (WINS_NEEDED - leader_wins) as usize;
+
+        println!("\n{}", "REMAINING SCHEDULE".bold());
+        println!("{}", "-".repeat(70));
+        for (game_number, start, game) in upcoming {
+            let if_necessary = if game_number > needed {
+                "(if necessary)".dimmed().to_string()
+            } else {
+                String::new()
+            };
+            println!(
+                "Game {}  {:<11} {:>8}  {} @ {}  {}",
+                game_number,
+                start
+                    .map(|t| t.format("%a %b %-d").to_string())
+                    .unwrap_or_default(),
+                start
+                    .map(|t| t.format("%-I:%M %p").to_string())
+                    .unwrap_or_default(),
+                game["awayTeam"]["abbrev"].as_str().unwrap_or(""),
+                game["homeTeam"]["abbrev"].as_str().unwrap_or(""),
+                if_necessary
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use colored::Colorize;
+use std::collections::HashMap;
+use crate::api::{NHL_STATS_API_URL, nhl_api_request};
+use crate::pbp::{clock_seconds, fetch_play_by_play, period_label, team_abbrev};
+
+// Each character of a shift bar covers this many seconds, so a 20 minute period is 24 wide
+const SECONDS_PER_COLUMN: i64 = 50;
+const REGULATION_PERIOD_SECONDS: i64 = 1200;
+// Shift chart rows with this type code are shifts, the rest are goal events
+const SHIFT_TYPE_CODE: i64 = 517;
+
+struct Shift {
+    period: i64,
+    start: i64,
+    end: i64,
+}
+
+struct Skater {
+    id: i64,
+    name: String,
+    number: i64,
+    position: String,
+    team: String,
+    shifts: Vec<Shift>,
+}
+
+impl Skater {
+    fn time_on_ice(&self) -> i64 {
+        self.shifts.iter().map(|s| s.end - s.start).sum()
+    }
+
+    fn is_forward(&self) -> bool {
+        matches!(self.position.as_str(), "C" | "L" | "R")
+    }
+}
+
+fn format_toi(seconds: i64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+// One period of a player's shift bar, a column is filled when the player was on the ice
+// for at least half of it
+fn shift_bar(skater: &Skater, period: i64, period_seconds: i64) -> String {
+    let columns = (period_seconds + SECONDS_PER_COLUMN - 1) / SECONDS_PER_COLUMN;
+    (0..columns)
+        .map(|col| {
+            let col_start = col * SECONDS_PER_COLUMN;
+            let col_end = col_start + SECONDS_PER_COLUMN;
+            let on_ice: i64 = skater
+                .shifts
+                .iter()
+                .filter(|s| s.period == period)
+                .map(|s| (s.end.min(col_end) - s.start.max(col_start)).max(0))
+                .sum();
+            if on_ice * 2 >= SECONDS_PER_COLUMN {
+                '█'
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+fn print_line_combinations(
+    title: &str,
+    combos: &HashMap<Vec<i64>, i64>,
+    skaters: &[Skater],
+    take: usize,
+) {
+    let mut combos: Vec<_> = combos.iter().collect();
+    combos.sort_by_key(|(_, seconds)| -**seconds);
+
+    println!("\n{}", title.bold());
+    for (players, seconds) in combos.into_iter().take(take) {
+        let names: Vec<_> = players
+            .iter()
+            .filter_map(|id| skaters.iter().find(|s| s.id == *id))
+            .map(|s| s.name.as_str())
+            .collect();
+        println!("{:>6}  {}", format_toi(*seconds), names.join(" - "));
+    }
+}
+
+pub async fn display_shifts(
+    client: &reqwest::Client,
+    game_id: &str,
+    team: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pbp = fetch_play_by_play(client, game_id).await?;
+    let url = format!(
+        "{}/shiftcharts?cayenneExp=gameId={}",
+        NHL_STATS_API_URL, game_id
+    );
+    let shift_chart = nhl_api_request(client, &url).await?;
+
+    let away_team = pbp["awayTeam"]["abbrev"].as_str().unwrap_or("AWY");
+    let home_team = pbp["homeTeam"]["abbrev"].as_str().unwrap_or("HOM");
+
+    // Build the roster from the play by play so we know positions and numbers
+    let mut skaters = Vec::new();
+    if let Some(roster) = pbp["rosterSpots"].as_array() {
+        for player in roster {
+            skaters.push(Skater {
+                id: player["playerId"].as_i64().unwrap_or(0),
+                name: format!(
+                    "{} {}",
+                    player["firstName"]["default"].as_str().unwrap_or(""),
+                    player["lastName"]["default"].as_str().unwrap_or("")
+                ),
+                number: player["sweaterNumber"].as_i64().unwrap_or(0),
+                position: player["positionCode"].as_str().unwrap_or("").to_string(),
+                team: team_abbrev(&pbp, player["teamId"].as_i64().unwrap_or(0)).to_string(),
+                shifts: Vec::new(),
+            });
+        }
+    }
+
+    if let Some(shifts) = shift_chart["data"].as_array() {
+        for shift in shifts {
+            if shift["typeCode"].as_i64() != Some(SHIFT_TYPE_CODE) {
+                continue;
+            }
+            let player_id = shift["playerId"].as_i64().unwrap_or(0);
+            if let Some(skater) = skaters.iter_mut().find(|s| s.id == player_id) {
+                skater.shifts.push(Shift {
+                    period: shift["period"].as_i64().unwrap_or(0),
+                    start: clock_seconds(shift["startTime"].as_str().unwrap_or("")),
+                    end: clock_seconds(shift["endTime"].as_str().unwrap_or("")),
+                });
+            }
+        }
+    }
+    skaters.retain(|s| !s.shifts.is_empty());
+
+    if skaters.is_empty() {
+        println!("No shift data found for this game");
+        return Ok(());
+    }
+
+    // Regulation periods are always 20 minutes, overtime runs until the last shift ends
+    let periods = skaters
+        .iter()
+        .flat_map(|s| s.shifts.iter().map(|shift| shift.period))
+        .max()
+        .unwrap_or(3)
+        .max(3);
+    let period_lengths: Vec<i64> = (1..=periods)
+        .map(|period| {
+            if period <= 3 {
+                return REGULATION_PERIOD_SECONDS;
+            }
+            let last = skaters
+                .iter()
+                .flat_map(|s| s.shifts.iter())
+                .filter(|s| s.period == period)
+                .map(|s| s.end)
+                .max()
+                .unwrap_or(0);
+            ((last + 59) / 60 * 60).max(60)
+        })
+        .collect();
+
+    // Line combinations are only counted at 5v5 so power play units don't skew them
+    let mut forward_lines: HashMap<&str, HashMap<Vec<i64>, i64>> = HashMap::new();
+    let mut defense_pairs: HashMap<&str, HashMap<Vec<i64>, i64>> = HashMap::new();
+
+    for (i, length) in period_lengths.iter().enumerate() {
+        let period = i as i64 + 1;
+        for second in 0..*length {
+            let on_ice: Vec<&Skater> = skaters
+                .iter()
+                .filter(|s| {
+                    s.shifts.iter().any(|shift| {
+                        shift.period == period && shift.start <= second && second < shift.end
+                    })
+                })
+                .collect();
+
+            let count = |team_name: &str, goalies: bool| {
+                on_ice
+                    .iter()
+                    .filter(|s| s.team == team_name && (s.position == "G") == goalies)
+                    .count()
+            };
+            if count(away_team, false) != 5
+                || count(home_team, false) != 5
+                || count(away_team, true) != 1
+                || count(home_team, true) != 1
+            {
+                continue;
+            }
+
+            for team_name in [away_team, home_team] {
+                let mut forwards: Vec<i64> = on_ice
+                    .iter()
+                    .filter(|s| s.team == team_name && s.is_forward())
+                    .map(|s| s.id)
+                    .collect();
+                let mut defense: Vec<i64> = on_ice
+                    .iter()
+                    .filter(|s| s.team == team_name && s.position == "D")
+                    .map(|s| s.id)
+                    .collect();
+
+                if forwards.len() == 3 {
+                    forwards.sort();
+                    *forward_lines
+                        .entry(team_name)
+                        .or_default()
+                        .entry(forwards)
+                        .or_default() += 1;
+                }
+                if defense.len() == 2 {
+                    defense.sort();
+                    *defense_pairs
+                        .entry(team_name)
+                        .or_default()
+                        .entry(defense)
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut header = format!("{:>2} {:<22} {:<3}", "#", "Player", "Pos");
+    for (i, length) in period_lengths.iter().enumerate() {
+        let width = ((length + SECONDS_PER_COLUMN - 1) / SECONDS_PER_COLUMN) as usize;
+        header.push_str(&format!(
+            "│{:^width$}",
+            period_label(&pbp, i as i64 + 1),
+            width = width
+        ));
+    }
+    header.push_str(&format!("│ {:>6}", "TOI"));
+    let separator = "=".repeat(header.chars().count());
+
+    for team_name in [away_team, home_team] {
+        if team.is_some_and(|t| !t.eq_ignore_ascii_case(team_name)) {
+            continue;
+        }
+
+        println!("\n{}", separator);
+        println!(
+            "{:^width$}",
+            format!("{} SHIFT CHART", team_name).bold(),
+            width = separator.len()
+        );
+        println!("{}", separator);
+        println!("{}", header.bold().underline());
+
+        let mut team_skaters: Vec<_> = skaters.iter().filter(|s| s.team == team_name).collect();
+        // Forwards, then defense, then goalies, most ice time first
+        team_skaters.sort_by_key(|s| {
+            let group = match s.position.as_str() {
+                "D" => 1,
+                "G" => 2,
+                _ => 0,
+            };
+            (group, -s.time_on_ice())
+        });
+
+        for skater in team_skaters {
+            let mut row = format!(
+                "{:>2} {:<22} {:<3}",
+                skater.number, skater.name, skater.position
+            );
+            for (i, length) in period_lengths.iter().enumerate() {
+                row.push('│');
+                row.push_str(&shift_bar(skater, i as i64 + 1, *length));
+            }
+            print!("{}", row);
+            println!("│ {:>6}", format_toi(skater.time_on_ice()).bold());
+        }
+
+        if let Some(lines) = forward_lines.get(team_name) {
+            print_line_combinations("Forward Lines (5v5)", lines, &skaters, 4);
+        }
+        if let Some(pairs) = defense_pairs.get(team_name) {
+            print_line_combinations("Defense Pairs (5v5)", pairs, &skaters, 3);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use colored::{ColoredString, Colorize};
+use serde_json::Value;
+use crate::pbp::{fetch_play_by_play, is_shot_attempt, period_label, shooting_team_id};
+
+// Rink is 200ft x 85ft with the origin at center ice. Terminal cells are roughly
+// twice as tall as they are wide so each row covers twice the distance of a column.
+const MAP_WIDTH: usize = 101;
+const MAP_HEIGHT: usize = 23;
+const RINK_HALF_LENGTH: f64 = 100.0;
+const RINK_HALF_WIDTH: f64 = 42.5;
+const BLUE_LINE: f64 = 25.0;
+const GOAL_LINE: f64 = 89.0;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Mark {
+    Empty,
+    Block,
+    Miss,
+    Save,
+    Goal,
+}
+
+impl Mark {
+    fn from_play(play: &Value) -> Mark {
+        match play["typeDescKey"].as_str().unwrap_or("") {
+            "goal" => Mark::Goal,
+            "shot-on-goal" => Mark::Save,
+            "missed-shot" => Mark::Miss,
+            "blocked-shot" => Mark::Block,
+            _ => Mark::Empty,
+        }
+    }
+
+    fn glyph(self) -> ColoredString {
+        match self {
+            Mark::Goal => "●".green().bold(),
+            Mark::Save => "○".white().bold(),
+            Mark::Miss => "×".yellow(),
+            Mark::Block => "▪".magenta(),
+            Mark::Empty => " ".normal(),
+        }
+    }
+}
+
+fn to_cell(x: f64, y: f64) -> (usize, usize) {
+    let col = (x + RINK_HALF_LENGTH) / (2.0 * RINK_HALF_LENGTH) * (MAP_WIDTH - 1) as f64;
+    let row = (RINK_HALF_WIDTH - y) / (2.0 * RINK_HALF_WIDTH) * (MAP_HEIGHT - 1) as f64;
+    (
+        (col.round().max(0.0) as usize).min(MAP_WIDTH - 1),
+        (row.round().max(0.0) as usize).min(MAP_HEIGHT - 1),
+    )
+}
+
+// Rink markings for a cell that has no shot in it
+fn rink_glyph(col: usize, row: usize) -> ColoredString {
+    let last_col = MAP_WIDTH - 1;
+    let last_row = MAP_HEIGHT - 1;
+    match (col, row) {
+        (0, 0) => return "╭".normal(),
+        (c, 0) if c == last_col => return "╮".normal(),
+        (0, r) if r == last_row => return "╰".normal(),
+        (c, r) if c == last_col && r == last_row => return "╯".normal(),
+        (0, _) => return "│".normal(),
+        (c, _) if c == last_col => return "│".normal(),
+        (_, 0) => return "─".normal(),
+        (_, r) if r == last_row => return "─".normal(),
+        _ => {}
+    }
+
+    let center = MAP_HEIGHT / 2;
+    let (blue_left, _) = to_cell(-BLUE_LINE, 0.0);
+    let (blue_right, _) = to_cell(BLUE_LINE, 0.0);
+    let (goal_left, _) = to_cell(-GOAL_LINE, 0.0);
+    let (goal_right, _) = to_cell(GOAL_LINE, 0.0);
+    let (center_col, _) = to_cell(0.0, 0.0);
+
+    if col == goal_left && row == center {
+        "]".red().bold()
+    } else if col == goal_right && row == center {
+        "[".red().bold()
+    } else if col == center_col {
+        "┊".red()
+    } else if col == blue_left || col == blue_right {
+        "│".blue()
+    } else if col == goal_left || col == goal_right {
+        "╎".red()
+    } else {
+        " ".normal()
+    }
+}
+
+pub async fn display_shotmap(
+    client: &reqwest::Client,
+    game_id: &str,
+    period: Option<i64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pbp = fetch_play_by_play(client, game_id).await?;
+    let separator = "=".repeat(MAP_WIDTH);
+
+    let away_id = pbp["awayTeam"]["id"].as_i64().unwrap_or(0);
+    let away_team = pbp["awayTeam"]["abbrev"].as_str().unwrap_or("AWY");
+    let home_team = pbp["homeTeam"]["abbrev"].as_str().unwrap_or("HOM");
+
+    let mut grid = vec![vec![Mark::Empty; MAP_WIDTH]; MAP_HEIGHT];
+    // Goals, saves, misses, blocks for each team
+    let mut away_totals = [0; 4];
+    let mut home_totals = [0; 4];
+
+    if let Some(plays) = pbp["plays"].as_array() {
+        for play in plays {
+            if !is_shot_attempt(play) {
+                continue;
+            }
+            let play_period = play["periodDescriptor"]["number"].as_i64().unwrap_or(0);
+            // Shootout attempts are all taken from the same spot and aren't part of the flow of play
+            if play["periodDescriptor"]["periodType"].as_str() == Some("SO") {
+                continue;
+            }
+            if period.is_some_and(|p| p != play_period) {
+                continue;
+            }
+            let (Some(x), Some(y)) = (
+                play["details"]["xCoord"].as_f64(),
+                play["details"]["yCoord"].as_f64(),
+            ) else {
+                continue;
+            };
+
+            let is_away = shooting_team_id(&pbp, play) == Some(away_id);
+
+            // The away team always attacks the left net and the home team the right net.
+            // Teams switch ends each period, so flip the coordinates when the home team
+            // defends the right side. Fall back to the sign of x if the side is missing.
+            let flip = match play["homeTeamDefendingSide"].as_str() {
+                Some("right") => true,
+                Some("left") => false,
+                _ => (x > 0.0) == is_away,
+            };
+            let (x, y) = if flip { (-x, -y) } else { (x, y) };
+
+            let mark = Mark::from_play(play);
+            let (col, row) = to_cell(x, y);
+            if mark > grid[row][col] {
+                grid[row][col] = mark;
+            }
+
+            let totals = if is_away {
+                &mut away_totals
+            } else {
+                &mut home_totals
+            };
+            match mark {
+                Mark::Goal => totals[0] += 1,
+                Mark::Save => totals[1] += 1,
+                Mark::Miss => totals[2] += 1,
+                Mark::Block => totals[3] += 1,
+                Mark::Empty => {}
+            }
+        }
+    }
+
+    println!("\n{}", separator);
+    let title = match period {
+        Some(p) => format!(
+            "{} @ {} - SHOT MAP ({})",
+            away_team,
+            home_team,
+            period_label(&pbp, p)
+        ),
+        None => format!("{} @ {} - SHOT MAP", away_team, home_team),
+    };
+    println!("{:^width$}", title.bold(), width = MAP_WIDTH);
+    println!("{}", separator);
+
+    let half = MAP_WIDTH / 2;
+    println!(
+        "{:<half$}{:>half$}",
+        format!("◀ {} attacking", away_team).bold(),
+        format!("{} attacking ▶", home_team).bold(),
+        half = half
+    );
+
+    for (row, marks) in grid.iter().enumerate() {
+        let mut line = String::new();
+        for (col, mark) in marks.iter().enumerate() {
+            let glyph = if *mark == Mark::Empty {
+                rink_glyph(col, row)
+            } else {
+                mark.glyph()
+            };
+            line.push_str(&glyph.to_string());
+        }
+        println!("{}", line);
+    }
+
+    println!(
+        "\n{} Goal   {} Saved   {} Missed   {} Blocked",
+        Mark::Goal.glyph(),
+        Mark::Save.glyph(),
+        Mark::Miss.glyph(),
+        Mark::Block.glyph()
+    );
+
+    let header = format!(
+        "{:<6} {:>8} {:>6} {:>6} {:>7} {:>8}",
+        "Team", "Attempts", "Goals", "Saved", "Missed", "Blocked"
+    );
+    println!("\n{}", header.bold().underline());
+    for (team, totals) in [(away_team, away_totals), (home_team, home_totals)] {
+        println!(
+            "{:<6} {:>8} {:>6} {:>6} {:>7} {:>8}",
+            team,
+            totals.iter().sum::<i32>(),
+            totals[0].to_string().green().bold(),
+            totals[1],
+            totals[2],
+            totals[3]
+        );
+    }
+
+    Ok(())
+}
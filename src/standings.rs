@@ -1,81 +1,344 @@
-use colored::Colorize;
+use chrono::NaiveDate;
+use colored::{ColoredString, Colorize};
 use serde_json::Value;
-use crate::api::{NHL_API_URL, nhl_api_request};
+use std::collections::HashMap;
+use crate::api::{NHL_API_URL, nhl_api_request, parse_season};
+use crate::clinch::{CLINCH_LEGEND, clinch_markers, load_games_remaining, print_magic_numbers};
+use crate::lottery::{LOTTERY_ODDS, parse_lottery_odds, print_lottery};
+use crate::odds::{OddsOptions, expected_points, fetch_remaining_schedule};
+use crate::tiebreakers::Tiebreaks;
+
+pub struct StandingsOptions {
+    /// Standings as of this date (YYYY-MM-DD)
+    pub date: Option<String>,
+    /// Final standings of this season (e.g. 2022-23)
+    pub season: Option<String>,
+    /// Start date of a standings diff (YYYY-MM-DD)
+    pub from: Option<String>,
+    /// End date of a standings diff (YYYY-MM-DD), defaults to today
+    pub to: Option<String>,
+    /// Column preset (basic, full, playoff) or a comma separated list of columns
+    pub columns: String,
+    /// Column to sort by instead of standings order
+    pub sort: Option<String>,
+    /// Sort ascending instead of descending
+    pub ascending: bool,
+    /// Show which tiebreaker separated teams that are tied on points
+    pub explain_ties: bool,
+    /// How the projected view extrapolates the rest of the season: "pace" (current points %)
+    /// or "schedule" (expected points against each remaining opponent)
+    pub projection: String,
+    /// Lottery odds (%) for the lottery view, worst team first, instead of the current table
+    pub lottery_odds: Option<String>,
+}
+
+// Regular season length, used for points pace when the schedule isn't loaded
+pub const SEASON_GAMES: i64 = 82;
+
+// Top 3 in each division make the playoffs, plus the next 2 best teams in the conference
+pub const DIVISION_PLAYOFF_SPOTS: usize = 3;
+pub const WILD_CARD_SPOTS: usize = 2;
+
+// Divisions print in this order, any other division names (older seasons) follow alphabetically
+const DIVISION_ORDER: [&str; 4] = ["Atlantic", "Metropolitan", "Central", "Pacific"];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Column {
+    GamesPlayed,
+    Wins,
+    Losses,
+    OtLosses,
+    Points,
+    PointPct,
+    RegulationWins,
+    RegulationPlusOtWins,
+    GoalsFor,
+    GoalsAgainst,
+    GoalDifferential,
+    Home,
+    Road,
+    LastTen,
+    Streak,
+    Shootout,
+    PointsPace,
+}
+
+impl Column {
+    fn from_name(name: &str) -> Option<Column> {
+        match name {
+            "gp" => Some(Column::GamesPlayed),
+            "w" => Some(Column::Wins),
+            "l" => Some(Column::Losses),
+            "otl" => Some(Column::OtLosses),
+            "pts" => Some(Column::Points),
+            "pct" => Some(Column::PointPct),
+            "rw" => Some(Column::RegulationWins),
+            "row" => Some(Column::RegulationPlusOtWins),
+            "gf" => Some(Column::GoalsFor),
+            "ga" => Some(Column::GoalsAgainst),
+            "diff" => Some(Column::GoalDifferential),
+            "home" => Some(Column::Home),
+            "road" => Some(Column::Road),
+            "l10" => Some(Column::LastTen),
+            "strk" | "streak" => Some(Column::Streak),
+            "so" => Some(Column::Shootout),
+            "pace" => Some(Column::PointsPace),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Column::GamesPlayed => "GP",
+            Column::Wins => "W",
+            Column::Losses => "L",
+            Column::OtLosses => "OTL",
+            Column::Points => "PTS",
+            Column::PointPct => "PCT",
+            Column::RegulationWins => "RW",
+            Column::RegulationPlusOtWins => "ROW",
+            Column::GoalsFor => "GF",
+            Column::GoalsAgainst => "GA",
+            Column::GoalDifferential => "DIFF",
+            Column::Home => "HOME",
+            Column::Road => "ROAD",
+            Column::LastTen => "L10",
+            Column::Streak => "STRK",
+            Column::Shootout => "S/O",
+            Column::PointsPace => "PACE",
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            Column::PointPct => 6,
+            Column::Home | Column::Road => 8,
+            Column::LastTen | Column::Shootout => 6,
+            Column::GoalDifferential | Column::Streak | Column::PointsPace => 4,
+            _ => 3,
+        }
+    }
+
+    // Numeric value used when sorting by this column. Records sort by the points earned in them.
+    fn sort_value(self, team: &Value) -> f64 {
+        let stat = |key: &str| team[key].as_i64().unwrap_or(0) as f64;
+        let record_points = |prefix: &str| {
+            2.0 * stat(&format!("{}Wins", prefix)) + stat(&format!("{}OtLosses", prefix))
+        };
+        match self {
+            Column::GamesPlayed => stat("gamesPlayed"),
+            Column::Wins => stat("wins"),
+            Column::Losses => stat("losses"),
+            Column::OtLosses => stat("otLosses"),
+            Column::Points => stat("points"),
+            Column::PointPct => team["pointPctg"].as_f64().unwrap_or(0.0),
+            Column::PointsPace => pace_points(team),
+            Column::RegulationWins => stat("regulationWins"),
+            Column::RegulationPlusOtWins => stat("regulationPlusOtWins"),
+            Column::GoalsFor => stat("goalFor"),
+            Column::GoalsAgainst => stat("goalAgainst"),
+            Column::GoalDifferential => stat("goalDifferential"),
+            Column::Home => record_points("home"),
+            Column::Road => record_points("road"),
+            Column::LastTen => record_points("l10"),
+            Column::Streak => match team["streakCode"].as_str() {
+                Some("W") => stat("streakCount"),
+                _ => -stat("streakCount"),
+            },
+            Column::Shootout => stat("shootoutWins") - stat("shootoutLosses"),
+        }
+    }
+
+    fn value(self, team: &Value) -> String {
+        let stat = |key: &str| team[key].as_i64().unwrap_or(0);
+        let record = |prefix: &str| {
+            format!(
+                "{}-{}-{}",
+                stat(&format!("{}Wins", prefix)),
+                stat(&format!("{}Losses", prefix)),
+                stat(&format!("{}OtLosses", prefix))
+            )
+        };
+        match self {
+            Column::GamesPlayed => stat("gamesPlayed").to_string(),
+            Column::Wins => stat("wins").to_string(),
+            Column::Losses => stat("losses").to_string(),
+            Column::OtLosses => stat("otLosses").to_string(),
+            Column::Points => stat("points").to_string(),
+            Column::PointPct => format!("{:.3}", team["pointPctg"].as_f64().unwrap_or(0.0)),
+            Column::RegulationWins => stat("regulationWins").to_string(),
+            Column::RegulationPlusOtWins => stat("regulationPlusOtWins").to_string(),
+            Column::GoalsFor => stat("goalFor").to_string(),
+            Column::GoalsAgainst => stat("goalAgainst").to_string(),
+            Column::GoalDifferential => format!("{:+}", stat("goalDifferential")),
+            Column::Home => record("home"),
+            Column::Road => record("road"),
+            Column::LastTen => record("l10"),
+            Column::Streak => format!(
+                "{}{}",
+                team["streakCode"].as_str().unwrap_or("-"),
+                stat("streakCount")
+            ),
+            Column::Shootout => format!("{}-{}", stat("shootoutWins"), stat("shootoutLosses")),
+            Column::PointsPace => format!("{:.0}", pace_points(team)),
+        }
+    }
+}
+
+/// Parse a column preset (basic, full, playoff) or a comma separated list of column names
+pub fn parse_columns(spec: &str) -> Result<Vec<Column>, Box<dyn std::error::Error>> {
+    let names = match spec.to_lowercase().as_str() {
+        "basic" => "gp,w,l,otl,pts,pct".to_string(),
+        "full" => "gp,w,l,otl,pts,pct,rw,row,gf,ga,diff,home,road,l10,strk,so,pace".to_string(),
+        "playoff" => "gp,pts,pct,rw,row,diff,l10,strk,pace".to_string(),
+        other => other.to_string(),
+    };
+
+    names
+        .split(',')
+        .map(|name| {
+            Column::from_name(name.trim()).ok_or_else(|| {
+                format!(
+                    "Invalid column '{}'. Use basic, full, playoff or a list of: \
+                     gp, w, l, otl, pts, pct, rw, row, gf, ga, diff, home, road, l10, strk, so, pace",
+                    name.trim()
+                )
+                .into()
+            })
+        })
+        .collect()
+}
+
+/// Standings order, or the order of a column when sorting by one. Teams that are equal
+/// in the sorted column keep their standings order.
+fn order_teams(teams: &mut [&Value], sort: Option<(Column, bool)>, tiebreaks: &Tiebreaks) {
+    tiebreaks.sort(teams);
+    if let Some((column, ascending)) = sort {
+        teams.sort_by(|a, b| {
+            let order = column.sort_value(a).total_cmp(&column.sort_value(b));
+            if ascending {
+                order
+            } else {
+                order.reverse()
+            }
+        });
+    }
+}
+
+fn standings_header(columns: &[Column]) -> String {
+    let mut header = format!("{:<24}", "Team");
+    for column in columns {
+        header.push_str(&format!(" {:>width$}", column.label(), width = column.width()));
+    }
+    header
+}
+
+/// Fetch the standings as of a date, the final standings of a season, or the current standings
+pub async fn fetch_standings(
+    client: &reqwest::Client,
+    date: Option<&str>,
+    season: Option<&str>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let date = match (date, season) {
+        (Some(date), _) => {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD", date))?;
+            date.to_string()
+        }
+        (None, Some(season)) => {
+            let season_id: i64 = parse_season(season)?.parse()?;
+            let url = format!("{}/standings-season", NHL_API_URL);
+            let seasons = nhl_api_request(client, &url).await?;
+            seasons["seasons"]
+                .as_array()
+                .and_then(|seasons| seasons.iter().find(|s| s["id"].as_i64() == Some(season_id)))
+                .and_then(|s| s["standingsEnd"].as_str())
+                .ok_or_else(|| format!("No standings found for season {}", season))?
+                .to_string()
+        }
+        (None, None) => "now".to_string(),
+    };
+
+    let url = format!("{}/standings/{}", NHL_API_URL, date);
+    nhl_api_request(client, &url).await
+}
 
 pub async fn display_standings(
     client: &reqwest::Client,
     format: &str,
+    options: &StandingsOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let url = format!("{}/standings/now", NHL_API_URL);
-    let standings = nhl_api_request(client, &url).await?;
-    let separator = "-".repeat(52);
-    let header = format!(
-        "{:<22} {:>3} {:>3} {:>3} {:>3} {:>3} {:>6}",
-        "Team", "GP", "W", "L", "OTL", "PTS", "PCT"
-    );
+    if format.eq_ignore_ascii_case("diff") {
+        let from = options
+            .from
+            .as_deref()
+            .ok_or("A start date is required for a standings diff, use --from YYYY-MM-DD")?;
+        return display_standings_diff(client, from, options.to.as_deref()).await;
+    }
 
-    match format.to_lowercase().as_str() {
-        "wildcard" => {
-            if let Some(standings) = standings["standings"].as_array() {
-                let mut teams_by_division: std::collections::HashMap<String, Vec<&Value>> =
-                    std::collections::HashMap::new();
-                let mut teams_by_conference: std::collections::HashMap<String, Vec<&Value>> =
-                    std::collections::HashMap::new();
-
-                // Group teams by division and conference
-                for team in standings {
-                    let division = team["divisionName"]
-                        .as_str()
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    let conference = team["conferenceName"]
-                        .as_str()
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    teams_by_division.entry(division).or_default().push(team);
-                    teams_by_conference
-                        .entry(conference)
-                        .or_default()
-                        .push(team);
-                }
+    let columns = parse_columns(&options.columns)?;
+    let mut standings =
+        fetch_standings(client, options.date.as_deref(), options.season.as_deref()).await?;
+    // Magic numbers and paces count the games each team really has left
+    let format = format.to_lowercase();
+    if format == "magic" || format == "projected" || columns.contains(&Column::PointsPace) {
+        if let Some(teams) = standings["standings"].as_array_mut() {
+            load_games_remaining(client, teams).await;
+        }
+    }
+    let tiebreaks = match standings["standings"].as_array() {
+        Some(teams) => Tiebreaks::load(client, teams).await?,
+        None => Tiebreaks::default(),
+    };
+    let markers = match standings["standings"].as_array() {
+        Some(teams) => clinch_markers(teams),
+        None => HashMap::new(),
+    };
+    // Tiebreakers only explain the order when teams are in standings order
+    let explain_ties = options.explain_ties && options.sort.is_none();
+    let sort = match &options.sort {
+        Some(name) => {
+            let column = Column::from_name(&name.to_lowercase())
+                .ok_or_else(|| format!("Invalid sort column '{}'", name))?;
+            Some((column, options.ascending))
+        }
+        None => None,
+    };
+    let header = standings_header(&columns);
+    let width = header.len().max(52);
+    let separator = "-".repeat(width);
 
-                // Sort conferences (Eastern first, then Western)
-                let mut conferences: Vec<_> = teams_by_conference.keys().collect();
-                conferences.sort();
+    if let Some(season) = &options.season {
+        println!("\n{:^width$}", format!("{} Final Standings", season).bold());
+    } else if let Some(date) = &options.date {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d.format("%B %d, %Y").to_string())
+            .unwrap_or_else(|_| date.to_string());
+        println!("\n{:^width$}", format!("Standings as of {}", date).bold());
+    }
 
-                for conference in conferences {
+    match format.as_str() {
+        "wildcard" => {
+            if let Some(standings) = standings["standings"].as_array() {
+                for conference in wildcard_standings(standings, &tiebreaks) {
                     println!("\n{}", separator);
                     println!(
-                        "{:^52}",
-                        format!("{} CONFERENCE", conference.to_uppercase())
+                        "{:^width$}",
+                        format!("{} CONFERENCE", conference.name.to_uppercase())
                     );
                     println!("{}", separator);
 
-                    // Get divisions in this conference
-                    let divisions: Vec<_> = teams_by_division
-                        .iter()
-                        .filter(|(_, teams)| {
-                            teams.first().map_or(false, |t| {
-                                t["conferenceName"].as_str().unwrap_or("") == conference
-                            })
-                        })
-                        .collect();
-
-                    // Collect division leaders to filter out from wild card standings
-                    let mut division_leaders = vec![];  
-
                     // Print division leaders
-                    for (division_name, teams) in divisions {
+                    for (division_name, teams) in &conference.divisions {
                         println!("\n{} {}", division_name.bold(), "Division".bold());
                         println!("{}", header.bold().underline());
 
-                        let mut teams = teams.clone();
-                        teams.sort_by_key(|team| -(team["points"].as_i64().unwrap_or(0)));
-
                         // Print top 3 teams
-                        for team in teams.iter().take(3) {
-                            division_leaders.push(team["teamName"]["default"].as_str().unwrap_or("Unknown"));
-                            print_team_stats(team);
+                        for (i, team) in teams.iter().enumerate().take(DIVISION_PLAYOFF_SPOTS) {
+                            print_team_stats(team, &columns, &markers);
+                            if explain_ties {
+                                print_tiebreak(teams, i, &tiebreaks);
+                            }
                         }
                     }
 
@@ -83,38 +346,90 @@ pub async fn display_standings(
                     println!("\n{}", "Wild Card".bold());
                     println!("{}", header.bold().underline());
 
-                    let mut all_teams = teams_by_conference[conference].clone();
-                    all_teams.sort_by_key(|team| -(team["points"].as_i64().unwrap_or(0)));
+                    // Print wild card teams and teams outside playoff spot
+                    for (i, team) in conference.wild_card.iter().enumerate() {
+                        if i == WILD_CARD_SPOTS {
+                            println!("{}", separator);
+                        }
+                        print_team_stats(team, &columns, &markers);
+                        if explain_ties {
+                            print_tiebreak(&conference.wild_card, i, &tiebreaks);
+                        }
+                    }
+                }
+            }
+        }
+        "magic" => {
+            if let Some(standings) = standings["standings"].as_array() {
+                print_magic_numbers(standings, &tiebreaks);
+            }
+            return Ok(());
+        }
+        "lottery" => {
+            let odds = match &options.lottery_odds {
+                Some(spec) => parse_lottery_odds(spec)?,
+                None => LOTTERY_ODDS.to_vec(),
+            };
+            if let Some(standings) = standings["standings"].as_array() {
+                print_lottery(standings, &tiebreaks, &odds);
+            }
+            return Ok(());
+        }
+        "projected" => {
+            if let Some(standings) = standings["standings"].as_array() {
+                let projection = match options.projection.to_lowercase().as_str() {
+                    "pace" => standings
+                        .iter()
+                        .map(|team| (team_abbrev(team).to_string(), pace_points(team)))
+                        .collect(),
+                    "schedule" => {
+                        if options.date.is_some() || options.season.is_some() {
+                            println!(
+                                "The schedule projection only works with the current standings"
+                            );
+                            return Ok(());
+                        }
+                        let schedule = fetch_remaining_schedule(client).await?;
+                        expected_points(standings, &schedule, &OddsOptions::default())
+                    }
+                    _ => {
+                        println!("Invalid projection. Use 'pace' or 'schedule'");
+                        return Ok(());
+                    }
+                };
+                print_projected_standings(standings, &projection, &tiebreaks);
+            }
+            return Ok(());
+        }
+        "division" => {
+            if let Some(standings) = standings["standings"].as_array() {
+                let teams_by_division = group_teams(standings, "divisionName");
+
+                for division in ordered_divisions(&teams_by_division) {
+                    println!("\n{}", separator);
+                    println!(
+                        "{:^width$}",
+                        format!("{} DIVISION", division.to_uppercase())
+                    );
+                    println!("{}", separator);
+                    println!("{}", header.bold().underline());
 
-                    // Get teams not in top 3 of their division
-                    let wild_card_teams: Vec<_> = all_teams.iter().filter(|team| !division_leaders.contains(&team["teamName"]["default"].as_str().unwrap_or("Unknown"))).collect();
+                    let mut teams = teams_by_division[division].clone();
+                    order_teams(&mut teams, sort, &tiebreaks);
 
-                    // Print wild card teams and teams outside playoff spot
-                    for (i, team) in wild_card_teams.iter().enumerate() {
-                        if i == 2 {
-                            println!("{}", "-".repeat(52));
+                    for (i, team) in teams.iter().enumerate() {
+                        print_team_stats(team, &columns, &markers);
+                        if explain_ties {
+                            print_tiebreak(&teams, i, &tiebreaks);
                         }
-                        print_team_stats(team);
                     }
                 }
             }
         }
         "conference" => {
             if let Some(standings) = standings["standings"].as_array() {
-                let mut teams_by_conference: std::collections::HashMap<String, Vec<&Value>> =
-                    std::collections::HashMap::new();
-
                 // Group teams by conference
-                for team in standings {
-                    let conference = team["conferenceName"]
-                        .as_str()
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    teams_by_conference
-                        .entry(conference)
-                        .or_default()
-                        .push(team);
-                }
+                let teams_by_conference = group_teams(standings, "conferenceName");
 
                 // Sort conferences (Eastern first, then Western)
                 let mut conferences: Vec<_> = teams_by_conference.keys().collect();
@@ -122,16 +437,19 @@ pub async fn display_standings(
 
                 for conference in conferences {
                     println!("\n{}", separator);
-                    println!("{:^52}", conference.to_uppercase());
+                    println!("{:^width$}", conference.to_uppercase());
                     println!("{}", separator);
                     println!("{}", header.bold().underline());
 
                     if let Some(teams) = teams_by_conference.get(conference) {
                         let mut teams = teams.clone();
-                        teams.sort_by_key(|team| -(team["points"].as_i64().unwrap_or(0)));
+                        order_teams(&mut teams, sort, &tiebreaks);
 
-                        for team in teams {
-                            print_team_stats(team);
+                        for (i, team) in teams.iter().enumerate() {
+                            print_team_stats(team, &columns, &markers);
+                            if explain_ties {
+                                print_tiebreak(&teams, i, &tiebreaks);
+                            }
                         }
                     }
                 }
@@ -140,37 +458,390 @@ pub async fn display_standings(
         "league" => {
             if let Some(standings) = standings["standings"].as_array() {
                 println!("\n{}", separator);
-                println!("{:^52}", "NHL STANDINGS");
+                println!("{:^width$}", "NHL STANDINGS");
                 println!("{}", separator);
                 println!("{}", header.bold().underline());
 
                 let mut teams: Vec<_> = standings.iter().collect();
-                teams.sort_by_key(|team| -(team["points"].as_i64().unwrap_or(0)));
+                order_teams(&mut teams, sort, &tiebreaks);
 
-                for team in teams {
-                    print_team_stats(team);
+                for (i, team) in teams.iter().enumerate() {
+                    print_team_stats(team, &columns, &markers);
+                    if explain_ties {
+                        print_tiebreak(&teams, i, &tiebreaks);
+                    }
                 }
             }
         }
         _ => {
-            println!("Invalid format. Use 'conference', 'wildcard', or 'league'");
+            println!(
+                "Invalid format. Use 'wildcard', 'division', 'conference', 'league', 'diff', 'magic', 'projected', or 'lottery'"
+            );
+            return Ok(());
         }
     }
 
+    if !markers.is_empty() {
+        println!("\n{}", CLINCH_LEGEND.dimmed());
+    }
+
     Ok(())
 }
 
-fn print_team_stats(team: &Value) {
-    let team_name = team["teamName"]["default"].as_str().unwrap_or("Unknown");
-    let games_played = team["gamesPlayed"].as_i64().unwrap_or(0);
-    let wins = team["wins"].as_i64().unwrap_or(0);
-    let losses = team["losses"].as_i64().unwrap_or(0);
-    let otl = team["otLosses"].as_i64().unwrap_or(0);
-    let points = team["points"].as_i64().unwrap_or(0);
-    let points_pct = team["pointPctg"].as_f64().unwrap_or(0.0);
+/// Group teams by a string field of the standings, e.g. "divisionName" or "conferenceName"
+pub fn group_teams<'a>(teams: &'a [Value], key: &str) -> HashMap<String, Vec<&'a Value>> {
+    let mut groups: HashMap<String, Vec<&Value>> = HashMap::new();
+    for team in teams {
+        let group = team[key].as_str().unwrap_or("Unknown").to_string();
+        groups.entry(group).or_default().push(team);
+    }
+    groups
+}
+
+/// A conference grouped the way the wildcard standings are: each division with its teams
+/// ranked, and every team outside the top 3 of its division ranked for the wild cards
+pub struct WildCardConference<'a> {
+    pub name: String,
+    pub divisions: Vec<(String, Vec<&'a Value>)>,
+    pub wild_card: Vec<&'a Value>,
+}
+
+/// Group teams into conferences (Eastern first, then Western) and rank them for the wildcard standings
+pub fn wildcard_standings<'a>(
+    teams: &'a [Value],
+    tiebreaks: &Tiebreaks,
+) -> Vec<WildCardConference<'a>> {
+    let teams_by_division = group_teams(teams, "divisionName");
+    let teams_by_conference = group_teams(teams, "conferenceName");
+
+    let mut conferences: Vec<_> = teams_by_conference.keys().collect();
+    conferences.sort();
+
+    conferences
+        .into_iter()
+        .map(|conference| {
+            let mut divisions = Vec::new();
+            let mut division_leaders = Vec::new();
+
+            // Divisions in this conference
+            for division in ordered_divisions(&teams_by_division) {
+                let mut division_teams = teams_by_division[division].clone();
+                if division_teams
+                    .first()
+                    .is_none_or(|t| t["conferenceName"].as_str().unwrap_or("Unknown") != conference)
+                {
+                    continue;
+                }
+                tiebreaks.sort(&mut division_teams);
+                for team in division_teams.iter().take(DIVISION_PLAYOFF_SPOTS) {
+                    division_leaders.push(team_abbrev(team));
+                }
+                divisions.push((division.clone(), division_teams));
+            }
+
+            // Teams not in the top 3 of their division
+            let mut wild_card: Vec<&Value> = teams_by_conference[conference]
+                .iter()
+                .copied()
+                .filter(|team| !division_leaders.contains(&team_abbrev(team)))
+                .collect();
+            tiebreaks.sort(&mut wild_card);
+
+            WildCardConference {
+                name: conference.clone(),
+                divisions,
+                wild_card,
+            }
+        })
+        .collect()
+}
+
+/// Division names in their canonical order
+pub fn ordered_divisions<T>(groups: &HashMap<String, T>) -> Vec<&String> {
+    let mut divisions: Vec<_> = groups.keys().collect();
+    divisions.sort_by_key(|name| {
+        let position = DIVISION_ORDER
+            .iter()
+            .position(|d| d == name)
+            .unwrap_or(DIVISION_ORDER.len());
+        (position, name.to_string())
+    });
+    divisions
+}
+
+pub fn team_abbrev(team: &Value) -> &str {
+    team["teamAbbrev"]["default"].as_str().unwrap_or("")
+}
+
+/// Regular season games a team has left, from its schedule when `gamesRemaining` has been
+/// loaded, otherwise from a full 82 game season
+pub fn games_remaining(team: &Value) -> i64 {
+    team["gamesRemaining"]
+        .as_i64()
+        .unwrap_or_else(|| (SEASON_GAMES - team["gamesPlayed"].as_i64().unwrap_or(0)).max(0))
+}
+
+// Final points at the team's current points % over the games it has left
+fn pace_points(team: &Value) -> f64 {
+    team["points"].as_i64().unwrap_or(0) as f64
+        + team["pointPctg"].as_f64().unwrap_or(0.0) * 2.0 * games_remaining(team) as f64
+}
+
+// Playoff seed labels (A1, M2, WC1, ...) for the teams currently holding a playoff spot
+fn playoff_seeds(teams: &[Value], tiebreaks: &Tiebreaks) -> HashMap<String, String> {
+    let mut seeds = HashMap::new();
+    for conference in wildcard_standings(teams, tiebreaks) {
+        for (division_name, division_teams) in &conference.divisions {
+            let initial = division_name.chars().next().unwrap_or('?');
+            for (i, team) in division_teams
+                .iter()
+                .take(DIVISION_PLAYOFF_SPOTS)
+                .enumerate()
+            {
+                seeds.insert(
+                    team_abbrev(team).to_string(),
+                    format!("{}{}", initial, i + 1),
+                );
+            }
+        }
+        for (i, team) in conference
+            .wild_card
+            .iter()
+            .take(WILD_CARD_SPOTS)
+            .enumerate()
+        {
+            seeds.insert(team_abbrev(team).to_string(), format!("WC{}", i + 1));
+        }
+    }
+    seeds
+}
+
+/// The wildcard table as it would finish with every team reaching its projected points,
+/// next to where each team sits in the current standings
+fn print_projected_standings(
+    teams: &[Value],
+    projection: &HashMap<String, f64>,
+    tiebreaks: &Tiebreaks,
+) {
+    let current_seeds = playoff_seeds(teams, tiebreaks);
+    let projected_points = |team: &Value| {
+        projection
+            .get(team_abbrev(team))
+            .copied()
+            .unwrap_or(team["points"].as_i64().unwrap_or(0) as f64)
+    };
 
+    // Every team finishes the season, so the projected points decide the order
+    let projected: Vec<Value> = teams
+        .iter()
+        .map(|team| {
+            let mut team = team.clone();
+            team["points"] = projected_points(&team).round().into();
+            team["gamesPlayed"] =
+                (team["gamesPlayed"].as_i64().unwrap_or(0) + games_remaining(&team)).into();
+            team
+        })
+        .collect();
+    let current: HashMap<&str, &Value> = teams.iter().map(|t| (team_abbrev(t), t)).collect();
+
+    let header = format!(
+        "{:<24} {:>3} {:>3} {:>5} {:>5} {:>4}",
+        "Team", "GP", "PTS", "P%", "PROJ", "NOW"
+    );
+    let width = header.len();
+    let separator = "-".repeat(width);
+
+    let print_team = |team: &Value| {
+        let Some(now) = current.get(team_abbrev(team)) else {
+            return;
+        };
+        let seed = current_seeds
+            .get(team_abbrev(team))
+            .map(String::as_str)
+            .unwrap_or("-");
+        println!(
+            "{:<24} {:>3} {:>3} {:>5.3} {:>5} {:>4}",
+            team["teamName"]["default"].as_str().unwrap_or("Unknown"),
+            now["gamesPlayed"].as_i64().unwrap_or(0),
+            now["points"].as_i64().unwrap_or(0),
+            now["pointPctg"].as_f64().unwrap_or(0.0),
+            format!("{:.0}", projected_points(now)).bold(),
+            seed
+        );
+    };
+
+    println!("\n{:^width$}", "Projected Final Standings".bold());
+    for conference in wildcard_standings(&projected, &Tiebreaks::default()) {
+        println!("\n{}", separator);
+        println!(
+            "{:^width$}",
+            format!("{} CONFERENCE", conference.name.to_uppercase())
+        );
+        println!("{}", separator);
+
+        for (division_name, division_teams) in &conference.divisions {
+            println!("\n{} {}", division_name.bold(), "Division".bold());
+            println!("{}", header.bold().underline());
+            for team in division_teams.iter().take(DIVISION_PLAYOFF_SPOTS) {
+                print_team(team);
+            }
+        }
+
+        println!("\n{}", "Wild Card".bold());
+        println!("{}", header.bold().underline());
+        for (i, team) in conference.wild_card.iter().enumerate() {
+            if i == WILD_CARD_SPOTS {
+                println!("{}", separator);
+            }
+            print_team(team);
+        }
+    }
+
+    println!(
+        "\n{}",
+        "PROJ: projected final points. NOW: current playoff seed.".dimmed()
+    );
+}
+
+// 1-based place of a team within a group of teams
+fn rank_in(teams: &[&Value], abbrev: &str, tiebreaks: &Tiebreaks) -> usize {
+    let mut teams = teams.to_vec();
+    tiebreaks.sort(&mut teams);
+    teams
+        .iter()
+        .position(|t| team_abbrev(t) == abbrev)
+        .map(|i| i + 1)
+        .unwrap_or(0)
+}
+
+fn rank_movement(from: usize, to: usize) -> ColoredString {
+    let movement = format!("{:>2} → {:<2}", from, to);
+    if to < from {
+        format!("{} ▲{}", movement, from - to).green()
+    } else if to > from {
+        format!("{} ▼{}", movement, to - from).red()
+    } else {
+        movement.normal()
+    }
+}
+
+async fn display_standings_diff(
+    client: &reqwest::Client,
+    from: &str,
+    to: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let from_standings = fetch_standings(client, Some(from), None).await?;
+    let to_standings = fetch_standings(client, to, None).await?;
+    let separator = "-".repeat(72);
+
+    let empty = Vec::new();
+    let from_teams = from_standings["standings"].as_array().unwrap_or(&empty);
+    let to_teams = to_standings["standings"].as_array().unwrap_or(&empty);
+
+    let from_tiebreaks = Tiebreaks::load(client, from_teams).await?;
+    let to_tiebreaks = Tiebreaks::load(client, to_teams).await?;
+
+    let from_divisions = group_teams(from_teams, "divisionName");
+    let from_conferences = group_teams(from_teams, "conferenceName");
+    let to_divisions = group_teams(to_teams, "divisionName");
+    let to_conferences = group_teams(to_teams, "conferenceName");
+
+    let to_label = to.unwrap_or("today");
+    println!("\n{}", separator);
     println!(
-        "{:<22} {:>3} {:>3} {:>3} {:>3} {:>3} {:>6.3}",
-        team_name, games_played, wins, losses, otl, points.to_string().bold(), points_pct
+        "{:^72}",
+        format!("STANDINGS CHANGE {} TO {}", from, to_label).bold()
     );
-}
\ No newline at end of file
+    println!("{}", separator);
+
+    let header = format!(
+        "{:<22} {:>3} {:>9} {:>5}  {:<12} {:<12}",
+        "Team", "GP", "Record", "+PTS", "Div Rank", "Conf Rank"
+    );
+
+    for division in ordered_divisions(&to_divisions) {
+        println!("\n{} {}", division.bold(), "Division".bold());
+        println!("{}", header.bold().underline());
+
+        let mut teams = to_divisions[division].clone();
+        to_tiebreaks.sort(&mut teams);
+
+        for team in teams {
+            let abbrev = team_abbrev(team);
+            let conference = team["conferenceName"].as_str().unwrap_or("Unknown");
+            let Some(before) = from_teams.iter().find(|t| team_abbrev(t) == abbrev) else {
+                continue;
+            };
+            let before_division = before["divisionName"].as_str().unwrap_or("Unknown");
+            let before_conference = before["conferenceName"].as_str().unwrap_or("Unknown");
+
+            let change =
+                |key: &str| team[key].as_i64().unwrap_or(0) - before[key].as_i64().unwrap_or(0);
+            let points = change("points");
+            let points_text = format!("{:+}", points);
+
+            let division_move = rank_movement(
+                from_divisions
+                    .get(before_division)
+                    .map_or(0, |teams| rank_in(teams, abbrev, &from_tiebreaks)),
+                rank_in(&to_divisions[division], abbrev, &to_tiebreaks),
+            );
+            let conference_move = rank_movement(
+                from_conferences
+                    .get(before_conference)
+                    .map_or(0, |teams| rank_in(teams, abbrev, &from_tiebreaks)),
+                to_conferences
+                    .get(conference)
+                    .map_or(0, |teams| rank_in(teams, abbrev, &to_tiebreaks)),
+            );
+
+            println!(
+                "{:<22} {:>3} {:>9} {:>5}  {:<12} {:<12}",
+                team["teamName"]["default"].as_str().unwrap_or("Unknown"),
+                change("gamesPlayed"),
+                format!("{}-{}-{}", change("wins"), change("losses"), change("otLosses")),
+                points_text.bold(),
+                division_move,
+                conference_move
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Note under a team that is tied on points with the team below it
+fn print_tiebreak(teams: &[&Value], index: usize, tiebreaks: &Tiebreaks) {
+    let (Some(team), Some(next)) = (teams.get(index), teams.get(index + 1)) else {
+        return;
+    };
+    if team["points"].as_i64() != next["points"].as_i64() {
+        return;
+    }
+    let reason = match tiebreaks.deciding(team, next, teams) {
+        Some(tiebreaker) => format!("on {}", tiebreaker.label()),
+        None => "with every tiebreaker equal".to_string(),
+    };
+    let note = format!(
+        "  ↳ ahead of {} {}",
+        next["teamName"]["default"].as_str().unwrap_or("Unknown"),
+        reason
+    );
+    println!("{}", note.dimmed());
+}
+
+fn print_team_stats(team: &Value, columns: &[Column], markers: &HashMap<String, char>) {
+    let team_name = team["teamName"]["default"].as_str().unwrap_or("Unknown");
+    let marker = markers.get(team_abbrev(team)).copied().unwrap_or(' ');
+    let mut row = format!("{} {:<22}", marker, team_name);
+    for column in columns {
+        let value = column.value(team);
+        let value = if *column == Column::Points {
+            value.bold()
+        } else {
+            value.normal()
+        };
+        row.push_str(&format!(" {:>width$}", value, width = column.width()));
+    }
+    println!("{}", row);
+}
@@ -0,0 +1,254 @@
+use colored::Colorize;
+use serde_json::{Value, json};
+use crate::api::{PLAYOFFS, REGULAR_SEASON, nhl_api_request, season_id, stats_report_url};
+
+// Rows requested per page, the stats API caps how many it returns at once
+const PAGE_SIZE: usize = 100;
+
+pub struct StatsOptions {
+    /// Report to query, e.g. summary, realtime or faceoffpercentages
+    pub report: String,
+    /// Season to query (e.g. 2022-23), the current season when not given
+    pub season: Option<String>,
+    /// Query the playoffs instead of the regular season
+    pub playoffs: bool,
+    /// Cayenne filter expressions, e.g. goals>=30 or positionCode="D"
+    pub filters: Vec<String>,
+    /// Comma separated fields to sort by
+    pub sort: Option<String>,
+    /// Sort ascending instead of descending
+    pub ascending: bool,
+    /// Comma separated fields to show
+    pub fields: Option<String>,
+    /// Most rows to show, 0 for every row
+    pub limit: usize,
+}
+
+// Report path, sort and columns used for each kind of query when none are given. The
+// default columns are fields of the summary report.
+fn kind_defaults(kind: &str) -> Option<(&'static str, &'static str, &'static [&'static str])> {
+    match kind {
+        "skaters" => Some((
+            "skater",
+            "points",
+            &[
+                "skaterFullName",
+                "teamAbbrevs",
+                "positionCode",
+                "gamesPlayed",
+                "goals",
+                "assists",
+                "points",
+                "plusMinus",
+                "pointsPerGame",
+            ],
+        )),
+        "goalies" => Some((
+            "goalie",
+            "wins",
+            &[
+                "goalieFullName",
+                "teamAbbrevs",
+                "gamesPlayed",
+                "wins",
+                "losses",
+                "otLosses",
+                "savePct",
+                "goalsAgainstAverage",
+                "shutouts",
+            ],
+        )),
+        "teams" => Some((
+            "team",
+            "points",
+            &[
+                "teamFullName",
+                "gamesPlayed",
+                "wins",
+                "losses",
+                "otLosses",
+                "points",
+                "goalsForPerGame",
+                "goalsAgainstPerGame",
+                "powerPlayPct",
+                "penaltyKillPct",
+            ],
+        )),
+        _ => None,
+    }
+}
+
+fn format_field(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) if number.is_f64() => {
+            let number = number.as_f64().unwrap_or(0.0);
+            if number.abs() < 1.0 {
+                format!("{:.3}", number)
+            } else {
+                format!("{:.2}", number)
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Every row of a stats API report, a page at a time, stopping once `limit` rows are
+/// fetched when there is a limit
+pub async fn fetch_report(
+    client: &reqwest::Client,
+    report: &str,
+    cayenne: &str,
+    sort: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let mut rows = Vec::new();
+
+    loop {
+        let page = limit.map_or(PAGE_SIZE, |limit| (limit - rows.len()).min(PAGE_SIZE));
+        let url = stats_report_url(report, cayenne, sort, rows.len(), page as i64)?;
+        let response = nhl_api_request(client, url.as_str()).await?;
+
+        let data = response["data"].as_array().cloned().unwrap_or_default();
+        let fetched = data.len();
+        rows.extend(data);
+
+        let total = response["total"].as_u64().unwrap_or(0) as usize;
+        let full = limit.is_some_and(|limit| rows.len() >= limit);
+        if fetched < page || full || rows.len() >= total {
+            break;
+        }
+    }
+
+    Ok(rows)
+}
+
+pub async fn display_stats(
+    client: &reqwest::Client,
+    kind: &str,
+    options: &StatsOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some((prefix, default_sort, default_fields)) = kind_defaults(&kind.to_lowercase()) else {
+        println!("Invalid stats type. Use 'skaters', 'goalies', or 'teams'");
+        return Ok(());
+    };
+    let report = format!("{}/{}", prefix, options.report);
+    let summary = options.report == "summary";
+
+    let season_id = season_id(client, options.season.as_deref()).await?;
+    let game_type = if options.playoffs {
+        PLAYOFFS
+    } else {
+        REGULAR_SEASON
+    };
+    let mut filters = vec![
+        format!("seasonId={}", season_id),
+        format!("gameTypeId={}", game_type),
+    ];
+    filters.extend(options.filters.iter().cloned());
+
+    // Other reports have different fields, so they are only sorted when asked to
+    let sort_fields = match (&options.sort, summary) {
+        (Some(sort), _) => Some(sort.as_str()),
+        (None, true) => Some(default_sort),
+        (None, false) => None,
+    };
+    let sort = sort_fields.map(|fields| {
+        let direction = if options.ascending { "ASC" } else { "DESC" };
+        let properties: Vec<Value> = fields
+            .split(',')
+            .map(|field| json!({ "property": field.trim(), "direction": direction }))
+            .collect();
+        Value::Array(properties).to_string()
+    });
+
+    let limit = (options.limit > 0).then_some(options.limit);
+    let rows = fetch_report(
+        client,
+        &report,
+        &filters.join(" and "),
+        sort.as_deref(),
+        limit,
+    )
+    .await?;
+
+    // Without a field list, other reports show every field of their first row
+    let fields: Vec<String> = match &options.fields {
+        Some(fields) => fields.split(',').map(|f| f.trim().to_string()).collect(),
+        None if summary => default_fields.iter().map(|f| f.to_string()).collect(),
+        None => rows
+            .first()
+            .and_then(|row| row.as_object())
+            .map(|row| row.keys().cloned().collect())
+            .unwrap_or_default(),
+    };
+
+    let table: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            fields
+                .iter()
+                .map(|field| format_field(&row[field]))
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            table
+                .iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(field.len())
+        })
+        .collect();
+    // Text columns line up on the left, numbers on the right
+    let numeric: Vec<bool> = fields
+        .iter()
+        .map(|field| rows.iter().all(|row| !row[field].is_string()))
+        .collect();
+    let format_row = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if numeric[i] {
+                    format!("{:>width$}", cell, width = widths[i])
+                } else {
+                    format!("{:<width$}", cell, width = widths[i])
+                }
+            })
+            .collect();
+        cells.join("  ")
+    };
+
+    let header = format_row(&fields);
+    let width = header.chars().count().max(60);
+    let separator = "-".repeat(width);
+    let season_type = if options.playoffs {
+        "Playoffs"
+    } else {
+        "Regular Season"
+    };
+
+    println!("\n{}", separator);
+    println!(
+        "{:^width$}",
+        format!("{} {}", kind.to_uppercase(), options.report.to_uppercase()).bold()
+    );
+    println!(
+        "{:^width$}",
+        format!("{}-{} {}", &season_id[0..4], &season_id[6..8], season_type)
+    );
+    println!("{}", separator);
+    println!("{}", header.bold().underline());
+    for row in &table {
+        println!("{}", format_row(row));
+    }
+    println!("\n{} rows", table.len());
+
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use crate::api::{NHL_API_URL, nhl_api_request};
+use crate::standings::team_abbrev;
+
+/// The NHL's regular season tiebreakers, in the order they are applied
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tiebreaker {
+    Points,
+    PointPct,
+    RegulationWins,
+    RegulationPlusOtWins,
+    Wins,
+    HeadToHead,
+    GoalDifferential,
+    GoalsFor,
+}
+
+impl Tiebreaker {
+    pub fn label(self) -> &'static str {
+        match self {
+            Tiebreaker::Points => "points",
+            Tiebreaker::PointPct => "points %",
+            Tiebreaker::RegulationWins => "regulation wins",
+            Tiebreaker::RegulationPlusOtWins => "regulation + OT wins",
+            Tiebreaker::Wins => "total wins",
+            Tiebreaker::HeadToHead => "head-to-head points",
+            Tiebreaker::GoalDifferential => "goal differential",
+            Tiebreaker::GoalsFor => "goals for",
+        }
+    }
+}
+
+// A completed game between two teams that may need head-to-head tiebreaking
+struct MatchupGame {
+    date: String,
+    home: String,
+    away: String,
+    home_points: i64,
+    away_points: i64,
+}
+
+/// Head-to-head results needed to break ties, loaded for teams that are still tied
+/// after the record based tiebreakers
+#[derive(Default)]
+pub struct Tiebreaks {
+    games: Vec<MatchupGame>,
+}
+
+fn stat(team: &Value, key: &str) -> i64 {
+    team[key].as_i64().unwrap_or(0)
+}
+
+// Compare two teams on the tiebreakers that only need their own record, returning the
+// first one that separates them. Greater is better.
+fn compare_record(a: &Value, b: &Value) -> Option<(Ordering, Tiebreaker)> {
+    let checks = [
+        (
+            stat(a, "points").cmp(&stat(b, "points")),
+            Tiebreaker::Points,
+        ),
+        // Points % without floats: a.points / a.gp vs b.points / b.gp
+        (
+            (stat(a, "points") * stat(b, "gamesPlayed"))
+                .cmp(&(stat(b, "points") * stat(a, "gamesPlayed"))),
+            Tiebreaker::PointPct,
+        ),
+        (
+            stat(a, "regulationWins").cmp(&stat(b, "regulationWins")),
+            Tiebreaker::RegulationWins,
+        ),
+        (
+            stat(a, "regulationPlusOtWins").cmp(&stat(b, "regulationPlusOtWins")),
+            Tiebreaker::RegulationPlusOtWins,
+        ),
+        (stat(a, "wins").cmp(&stat(b, "wins")), Tiebreaker::Wins),
+    ];
+    checks
+        .into_iter()
+        .find(|(order, _)| *order != Ordering::Equal)
+}
+
+impl Tiebreaks {
+    /// Load the head-to-head games between teams that are tied on every record based tiebreaker
+    pub async fn load(
+        client: &reqwest::Client,
+        teams: &[Value],
+    ) -> Result<Tiebreaks, Box<dyn std::error::Error>> {
+        let mut tied: HashSet<&str> = HashSet::new();
+        for (i, a) in teams.iter().enumerate() {
+            for b in &teams[i + 1..] {
+                if compare_record(a, b).is_none() {
+                    tied.insert(team_abbrev(a));
+                    tied.insert(team_abbrev(b));
+                }
+            }
+        }
+
+        // Every tied team's schedule is fetched at once, early in the season that can be the
+        // whole league
+        let mut requests = tokio::task::JoinSet::new();
+        for team in teams.iter().filter(|t| tied.contains(team_abbrev(t))) {
+            let url = format!(
+                "{}/club-schedule-season/{}/{}",
+                NHL_API_URL,
+                team_abbrev(team),
+                stat(team, "seasonId")
+            );
+            let client = client.clone();
+            requests.spawn(async move {
+                nhl_api_request(&client, &url)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+        }
+        let mut schedules = Vec::new();
+        while let Some(schedule) = requests.join_next().await {
+            schedules.push(schedule??);
+        }
+
+        // Only games played up to the date of these standings count
+        let cutoff = teams
+            .first()
+            .and_then(|team| team["date"].as_str())
+            .unwrap_or("9999-12-31");
+        let mut games = Vec::new();
+        let mut seen = HashSet::new();
+        for schedule in &schedules {
+            for game in schedule["games"].as_array().into_iter().flatten() {
+                let home = game["homeTeam"]["abbrev"].as_str().unwrap_or("");
+                let away = game["awayTeam"]["abbrev"].as_str().unwrap_or("");
+                let date = game["gameDate"].as_str().unwrap_or("");
+                let finished = matches!(game["gameState"].as_str(), Some("FINAL") | Some("OFF"));
+                if game["gameType"].as_i64() != Some(2)
+                    || !finished
+                    || date > cutoff
+                    || !tied.contains(home)
+                    || !tied.contains(away)
+                    || !seen.insert(game["id"].as_i64().unwrap_or(0))
+                {
+                    continue;
+                }
+
+                let home_score = game["homeTeam"]["score"].as_i64().unwrap_or(0);
+                let away_score = game["awayTeam"]["score"].as_i64().unwrap_or(0);
+                let extra_time = game["gameOutcome"]["lastPeriodType"].as_str() != Some("REG");
+                let loser_points = if extra_time { 1 } else { 0 };
+                let (home_points, away_points) = if home_score > away_score {
+                    (2, loser_points)
+                } else {
+                    (loser_points, 2)
+                };
+
+                games.push(MatchupGame {
+                    date: date.to_string(),
+                    home: home.to_string(),
+                    away: away.to_string(),
+                    home_points,
+                    away_points,
+                });
+            }
+        }
+
+        Ok(Tiebreaks { games })
+    }
+
+    // Points earned by `a` and the number of games counted between two teams. When they played
+    // an odd number of games, the first game in the city that hosted the extra game doesn't count.
+    fn pair_points(&self, a: &str, b: &str) -> (i64, i64) {
+        let mut games: Vec<&MatchupGame> = self
+            .games
+            .iter()
+            .filter(|g| (g.home == a && g.away == b) || (g.home == b && g.away == a))
+            .collect();
+        games.sort_by(|x, y| x.date.cmp(&y.date));
+
+        if games.len() % 2 == 1 {
+            let a_home = games.iter().filter(|g| g.home == a).count();
+            let extra_host = if a_home * 2 > games.len() { a } else { b };
+            if let Some(index) = games.iter().position(|g| g.home == extra_host) {
+                games.remove(index);
+            }
+        }
+
+        let a_points = games
+            .iter()
+            .map(|g| if g.home == a { g.home_points } else { g.away_points })
+            .sum();
+        (a_points, games.len() as i64)
+    }
+
+    // Points % earned by a team in games against the other teams it is tied with
+    fn head_to_head(&self, team: &Value, tied: &[&Value]) -> f64 {
+        let mut points = 0;
+        let mut games = 0;
+        for other in tied.iter().filter(|t| team_abbrev(t) != team_abbrev(team)) {
+            let (team_points, played) = self.pair_points(team_abbrev(team), team_abbrev(other));
+            points += team_points;
+            games += played;
+        }
+        if games == 0 {
+            0.0
+        } else {
+            points as f64 / (2 * games) as f64
+        }
+    }
+
+    // Compare two teams that are tied on their records using the remaining tiebreakers
+    fn compare_tied(
+        &self,
+        a: &Value,
+        b: &Value,
+        tied: &[&Value],
+    ) -> Option<(Ordering, Tiebreaker)> {
+        let checks = [
+            (
+                self.head_to_head(a, tied)
+                    .total_cmp(&self.head_to_head(b, tied)),
+                Tiebreaker::HeadToHead,
+            ),
+            (
+                stat(a, "goalDifferential").cmp(&stat(b, "goalDifferential")),
+                Tiebreaker::GoalDifferential,
+            ),
+            (
+                stat(a, "goalFor").cmp(&stat(b, "goalFor")),
+                Tiebreaker::GoalsFor,
+            ),
+        ];
+        checks
+            .into_iter()
+            .find(|(order, _)| *order != Ordering::Equal)
+    }
+
+    /// Order teams from first to last place using the NHL tiebreakers
+    pub fn sort(&self, teams: &mut [&Value]) {
+        teams.sort_by(|a, b| compare_record(b, a).map_or(Ordering::Equal, |(order, _)| order));
+
+        // Break the remaining ties within each block of teams with identical records
+        let mut start = 0;
+        while start < teams.len() {
+            let mut end = start + 1;
+            while end < teams.len() && compare_record(teams[start], teams[end]).is_none() {
+                end += 1;
+            }
+            if end - start > 1 {
+                let tied = teams[start..end].to_vec();
+                teams[start..end].sort_by(|a, b| {
+                    self.compare_tied(b, a, &tied)
+                        .map_or(Ordering::Equal, |(order, _)| order)
+                });
+            }
+            start = end;
+        }
+    }
+
+    /// The tiebreaker that put `above` ahead of `below` when they are tied on points,
+    /// within the group of teams they are ranked in
+    pub fn deciding(&self, above: &Value, below: &Value, group: &[&Value]) -> Option<Tiebreaker> {
+        match compare_record(above, below) {
+            Some((_, Tiebreaker::Points)) => None,
+            Some((_, tiebreaker)) => Some(tiebreaker),
+            None => {
+                let tied: Vec<&Value> = group
+                    .iter()
+                    .copied()
+                    .filter(|t| compare_record(t, above).is_none())
+                    .collect();
+                self.compare_tied(above, below, &tied)
+                    .map(|(_, tiebreaker)| tiebreaker)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn team(abbrev: &str, points: i64, games: i64, regulation_wins: i64, goal_diff: i64) -> Value {
+        json!({
+            "teamAbbrev": { "default": abbrev },
+            "points": points,
+            "gamesPlayed": games,
+            "regulationWins": regulation_wins,
+            "regulationPlusOtWins": regulation_wins,
+            "wins": regulation_wins,
+            "goalDifferential": goal_diff,
+            "goalFor": 200,
+        })
+    }
+
+    fn game(date: &str, home: &str, away: &str, home_points: i64, away_points: i64) -> MatchupGame {
+        MatchupGame {
+            date: date.to_string(),
+            home: home.to_string(),
+            away: away.to_string(),
+            home_points,
+            away_points,
+        }
+    }
+
+    #[test]
+    fn compare_record_applies_tiebreakers_in_order() {
+        let a = team("AAA", 90, 80, 35, 0);
+        assert_eq!(
+            compare_record(&a, &team("BBB", 88, 80, 40, 0)),
+            Some((Ordering::Greater, Tiebreaker::Points))
+        );
+        // Same points in fewer games is a better points %
+        assert_eq!(
+            compare_record(&a, &team("BBB", 90, 81, 40, 0)),
+            Some((Ordering::Greater, Tiebreaker::PointPct))
+        );
+        assert_eq!(
+            compare_record(&a, &team("BBB", 90, 80, 36, 0)),
+            Some((Ordering::Less, Tiebreaker::RegulationWins))
+        );
+        assert_eq!(compare_record(&a, &team("BBB", 90, 80, 35, 10)), None);
+    }
+
+    #[test]
+    fn pair_points_drops_first_game_in_extra_host_city() {
+        let tiebreaks = Tiebreaks {
+            games: vec![
+                game("2024-01-10", "AAA", "BBB", 2, 0),
+                game("2023-11-01", "AAA", "BBB", 0, 2),
+                game("2023-12-01", "BBB", "AAA", 1, 2),
+            ],
+        };
+        // AAA hosted two of the three games, so its first home game doesn't count
+        assert_eq!(tiebreaks.pair_points("AAA", "BBB"), (4, 2));
+        assert_eq!(tiebreaks.pair_points("BBB", "AAA"), (1, 2));
+    }
+
+    #[test]
+    fn sort_breaks_tied_block_on_head_to_head() {
+        let top = team("TOP", 100, 82, 45, 0);
+        let a = team("AAA", 90, 82, 40, -5);
+        let b = team("BBB", 90, 82, 40, 20);
+        let bottom = team("LOW", 70, 82, 30, 0);
+        let tiebreaks = Tiebreaks {
+            games: vec![
+                game("2023-11-01", "AAA", "BBB", 2, 0),
+                game("2023-12-01", "BBB", "AAA", 0, 2),
+            ],
+        };
+
+        let mut teams = vec![&bottom, &b, &top, &a];
+        tiebreaks.sort(&mut teams);
+        let order: Vec<&str> = teams.iter().map(|t| team_abbrev(t)).collect();
+        assert_eq!(order, ["TOP", "AAA", "BBB", "LOW"]);
+        assert_eq!(
+            tiebreaks.deciding(&a, &b, &teams),
+            Some(Tiebreaker::HeadToHead)
+        );
+    }
+}
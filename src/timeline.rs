@@ -0,0 +1,193 @@
+use colored::Colorize;
+use crate::pbp::{
+    clock_seconds, fetch_play_by_play, is_shot_attempt, period_label, player_names,
+    shooting_team_id, team_abbrev,
+};
+
+// Each row of the timeline covers one minute of game time
+const BUCKET_SECONDS: i64 = 60;
+const BAR_WIDTH: usize = 25;
+
+struct Bucket {
+    period: i64,
+    index: i64,
+    away_attempts: i64,
+    home_attempts: i64,
+    markers: Vec<String>,
+}
+
+impl Bucket {
+    fn new(period: i64, index: i64) -> Bucket {
+        Bucket {
+            period,
+            index,
+            away_attempts: 0,
+            home_attempts: 0,
+            markers: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_timeline(
+    client: &reqwest::Client,
+    game_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pbp = fetch_play_by_play(client, game_id).await?;
+    let names = player_names(&pbp);
+
+    let away_id = pbp["awayTeam"]["id"].as_i64().unwrap_or(0);
+    let away_team = pbp["awayTeam"]["abbrev"].as_str().unwrap_or("AWY");
+    let home_team = pbp["homeTeam"]["abbrev"].as_str().unwrap_or("HOM");
+
+    let mut buckets: Vec<Bucket> = Vec::new();
+
+    if let Some(plays) = pbp["plays"].as_array() {
+        for play in plays {
+            let type_key = play["typeDescKey"].as_str().unwrap_or("");
+            let period = play["periodDescriptor"]["number"].as_i64().unwrap_or(0);
+            if play["periodDescriptor"]["periodType"].as_str() == Some("SO") {
+                continue;
+            }
+            if !is_shot_attempt(play) && type_key != "penalty" {
+                continue;
+            }
+
+            let elapsed = clock_seconds(play["timeInPeriod"].as_str().unwrap_or(""));
+            let bucket = elapsed / BUCKET_SECONDS;
+            let index = match buckets
+                .iter()
+                .position(|b| b.period == period && b.index == bucket)
+            {
+                Some(index) => index,
+                None => {
+                    buckets.push(Bucket::new(period, bucket));
+                    buckets.len() - 1
+                }
+            };
+
+            let player = |key: &str| {
+                play["details"][key]
+                    .as_i64()
+                    .and_then(|id| names.get(&id))
+                    .cloned()
+                    .unwrap_or_default()
+            };
+
+            if is_shot_attempt(play) {
+                if shooting_team_id(&pbp, play) == Some(away_id) {
+                    buckets[index].away_attempts += 1;
+                } else {
+                    buckets[index].home_attempts += 1;
+                }
+            }
+
+            let owner = team_abbrev(
+                &pbp,
+                play["details"]["eventOwnerTeamId"].as_i64().unwrap_or(0),
+            );
+            if type_key == "goal" {
+                let marker = format!("● {} goal {}", owner, player("scoringPlayerId"));
+                buckets[index]
+                    .markers
+                    .push(marker.green().bold().to_string());
+            } else if type_key == "penalty" {
+                let marker = format!(
+                    "▲ {} {} {}",
+                    owner,
+                    play["details"]["descKey"]
+                        .as_str()
+                        .unwrap_or("penalty")
+                        .replace('-', " "),
+                    player("committedByPlayerId")
+                );
+                buckets[index].markers.push(marker.yellow().to_string());
+            }
+        }
+    }
+
+    // Fill in quiet minutes so every period shows its full length
+    let last_period = buckets.iter().map(|b| b.period).max().unwrap_or(3).max(3);
+    for period in 1..=last_period {
+        let last_bucket = if period <= 3 {
+            1200 / BUCKET_SECONDS - 1
+        } else {
+            buckets
+                .iter()
+                .filter(|b| b.period == period)
+                .map(|b| b.index)
+                .max()
+                .unwrap_or(0)
+        };
+        for bucket in 0..=last_bucket {
+            if !buckets
+                .iter()
+                .any(|b| b.period == period && b.index == bucket)
+            {
+                buckets.push(Bucket::new(period, bucket));
+            }
+        }
+    }
+    buckets.sort_by_key(|b| (b.period, b.index));
+
+    let away_total: i64 = buckets.iter().map(|b| b.away_attempts).sum();
+    let home_total: i64 = buckets.iter().map(|b| b.home_attempts).sum();
+    let scale = away_total.max(home_total).max(1) as f64 / BAR_WIDTH as f64;
+
+    let separator = "=".repeat(80);
+    println!("\n{}", separator);
+    println!(
+        "{:^80}",
+        format!("{} @ {} - SHOT ATTEMPT TIMELINE", away_team, home_team).bold()
+    );
+    println!("{}", separator);
+    println!(
+        "{:<10} {:>3} {:>width$}│{:<width$} {:<3}",
+        "",
+        away_team.bold(),
+        "",
+        "",
+        home_team.bold(),
+        width = BAR_WIDTH
+    );
+
+    let mut away_cumulative = 0;
+    let mut home_cumulative = 0;
+    let mut current_period = 0;
+
+    for bucket in buckets {
+        if bucket.period != current_period {
+            current_period = bucket.period;
+            let label = format!(" {} ", period_label(&pbp, bucket.period));
+            println!("{}", format!("{:-^80}", label).bold());
+        }
+        away_cumulative += bucket.away_attempts;
+        home_cumulative += bucket.home_attempts;
+
+        let away_bar = "█".repeat((away_cumulative as f64 / scale).round() as usize);
+        let home_bar = "█".repeat((home_cumulative as f64 / scale).round() as usize);
+        let time = format!("{:02}:00", bucket.index + 1);
+
+        println!(
+            "{:<10} {:>3} {:>width$}│{:<width$} {:<3} {}",
+            time,
+            away_cumulative,
+            away_bar.red(),
+            home_bar.blue(),
+            home_cumulative,
+            bucket.markers.join("  "),
+            width = BAR_WIDTH
+        );
+    }
+
+    println!(
+        "\n{} {}   {} {}   {} Goal   {} Penalty",
+        "█".red(),
+        away_team,
+        "█".blue(),
+        home_team,
+        "●".green().bold(),
+        "▲".yellow()
+    );
+
+    Ok(())
+}